@@ -0,0 +1,11 @@
+fn main() {
+    let cfg = traxer::Config::new("traxer-split")
+        .stream(traxer::Stream::Split)
+        .color(traxer::Color::Never)
+        .error_report(false);
+    traxer::init(cfg);
+
+    traxer::error!("error message");
+    traxer::warn!("warn message");
+    traxer::info!("info message");
+}
@@ -1,10 +1,15 @@
+use crate::writer::{CustomWriter, FileSink, NonBlocking};
+#[cfg(unix)]
+use crate::writer::{JournaldSink, SyslogSink};
 use serde_json::Value;
 use std::borrow::Cow;
+use tracing_subscriber::fmt::MakeWriter;
 
 #[derive(Clone, Copy, Debug)]
 pub enum OutputFormat {
     Plain,
     Json,
+    Logfmt,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -14,10 +19,168 @@ pub enum Color {
     Never,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeStyle {
+    None,
+    /// Local time with its UTC offset. Falls back to UTC (`Z`) where the
+    /// offset is unavailable, which includes every non-Unix target.
+    Local,
+    Utc,
+    Uptime,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimestampFormat {
+    UnixMillis,
+    UnixSeconds,
+    UnixNanos,
+    Rfc3339,
+}
+
+#[derive(Clone, Debug)]
 pub enum Stream {
     Stdout,
     Stderr,
+    Split,
+    File(FileSink),
+    Custom(CustomWriter),
+    #[cfg(unix)]
+    Fd(std::os::fd::RawFd),
+    #[cfg(unix)]
+    Syslog(SyslogSink),
+    #[cfg(unix)]
+    Journald(JournaldSink),
+}
+
+impl Stream {
+    pub fn custom<M>(make_writer: M) -> Self
+    where
+        M: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+    {
+        Self::Custom(CustomWriter::new(make_writer))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Output {
+    pub output_format: OutputFormat,
+    pub stream: Stream,
+    pub color: Color,
+    /// Filter for this output only. When set it takes precedence over both
+    /// `RUST_LOG` and the config-wide filter, and `Handle` reloads leave it
+    /// untouched. When `None` the output shares the config-wide filter, where
+    /// `RUST_LOG` wins over `Config::filter_directives`, which wins over
+    /// `verbose`/`quiet`.
+    pub filter_directives: Option<Cow<'static, str>>,
+}
+
+impl Output {
+    pub fn new(stream: Stream) -> Self {
+        Self {
+            output_format: OutputFormat::Plain,
+            stream,
+            color: Color::Auto,
+            filter_directives: None,
+        }
+    }
+    pub fn plain(mut self) -> Self {
+        self.output_format = OutputFormat::Plain;
+        self
+    }
+    pub fn json(mut self) -> Self {
+        self.output_format = OutputFormat::Json;
+        self
+    }
+    pub fn logfmt(mut self) -> Self {
+        self.output_format = OutputFormat::Logfmt;
+        self
+    }
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+    pub fn with_filter_directives(mut self, directives: impl Into<Cow<'static, str>>) -> Self {
+        self.filter_directives = Some(directives.into());
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportTheme {
+    Dark,
+    Light,
+    Blank,
+}
+
+#[derive(Clone, Debug)]
+pub struct ReportHook {
+    pub theme: ReportTheme,
+    #[cfg(feature = "issue-url")]
+    pub issue_url: Option<Cow<'static, str>>,
+    #[cfg(feature = "issue-url")]
+    pub version: Option<Cow<'static, str>>,
+    pub hidden_frames: Vec<Cow<'static, str>>,
+    pub env_section: bool,
+    pub location_section: bool,
+}
+
+impl ReportHook {
+    pub fn new() -> Self {
+        Self {
+            theme: ReportTheme::Dark,
+            #[cfg(feature = "issue-url")]
+            issue_url: None,
+            #[cfg(feature = "issue-url")]
+            version: None,
+            hidden_frames: Vec::new(),
+            env_section: true,
+            location_section: true,
+        }
+    }
+    pub fn theme(mut self, theme: ReportTheme) -> Self {
+        self.theme = theme;
+        self
+    }
+    pub fn dark(mut self) -> Self {
+        self.theme = ReportTheme::Dark;
+        self
+    }
+    pub fn light(mut self) -> Self {
+        self.theme = ReportTheme::Light;
+        self
+    }
+    pub fn blank(mut self) -> Self {
+        self.theme = ReportTheme::Blank;
+        self
+    }
+    #[cfg(feature = "issue-url")]
+    pub fn issue_url(mut self, url: impl Into<Cow<'static, str>>) -> Self {
+        self.issue_url = Some(url.into());
+        self
+    }
+    #[cfg(feature = "issue-url")]
+    pub fn version(mut self, version: impl Into<Cow<'static, str>>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+    pub fn hide_frames(mut self, prefix: impl Into<Cow<'static, str>>) -> Self {
+        self.hidden_frames.push(prefix.into());
+        self
+    }
+    pub fn env_section(mut self, enabled: bool) -> Self {
+        self.env_section = enabled;
+        self
+    }
+    pub fn location_section(mut self, enabled: bool) -> Self {
+        self.location_section = enabled;
+        self
+    }
+}
+
+impl Default for ReportHook {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -29,11 +192,29 @@ pub struct ConfigOverride {
     pub quiet: Option<u8>,
     pub filter_directives: Option<Cow<'static, str>>,
     pub base_fields: Option<Vec<(Cow<'static, str>, Value)>>,
+    pub outputs: Option<Vec<Output>>,
+    pub non_blocking: Option<NonBlocking>,
+    pub time_style: Option<TimeStyle>,
+    pub timestamp_format: Option<TimestampFormat>,
+    pub timestamp_key: Option<Cow<'static, str>>,
     pub include_pid: Option<bool>,
     pub include_exe: Option<bool>,
     pub include_version: Option<bool>,
+    pub include_target: Option<bool>,
+    pub include_module_path: Option<bool>,
+    pub include_file: Option<bool>,
+    pub include_line: Option<bool>,
+    pub include_thread_name: Option<bool>,
+    pub include_thread_id: Option<bool>,
     pub span: Option<bool>,
+    pub flatten_span_fields: Option<bool>,
+    pub span_path: Option<bool>,
     pub error_report: Option<bool>,
+    pub report_hook: Option<ReportHook>,
+    #[cfg(feature = "log")]
+    pub log_bridge: Option<bool>,
+    #[cfg(feature = "signal")]
+    pub signal_verbosity: Option<bool>,
 }
 
 impl ConfigOverride {
@@ -68,6 +249,26 @@ impl ConfigOverride {
         self.base_fields = Some(base_fields);
         self
     }
+    pub fn outputs(mut self, outputs: Vec<Output>) -> Self {
+        self.outputs = Some(outputs);
+        self
+    }
+    pub fn non_blocking(mut self, non_blocking: NonBlocking) -> Self {
+        self.non_blocking = Some(non_blocking);
+        self
+    }
+    pub fn time_style(mut self, time_style: TimeStyle) -> Self {
+        self.time_style = Some(time_style);
+        self
+    }
+    pub fn timestamp_format(mut self, timestamp_format: TimestampFormat) -> Self {
+        self.timestamp_format = Some(timestamp_format);
+        self
+    }
+    pub fn timestamp_key(mut self, key: impl Into<Cow<'static, str>>) -> Self {
+        self.timestamp_key = Some(key.into());
+        self
+    }
     pub fn include_pid(mut self, enabled: bool) -> Self {
         self.include_pid = Some(enabled);
         self
@@ -80,14 +281,60 @@ impl ConfigOverride {
         self.include_version = Some(enabled);
         self
     }
+    pub fn include_target(mut self, enabled: bool) -> Self {
+        self.include_target = Some(enabled);
+        self
+    }
+    pub fn include_module_path(mut self, enabled: bool) -> Self {
+        self.include_module_path = Some(enabled);
+        self
+    }
+    pub fn include_file(mut self, enabled: bool) -> Self {
+        self.include_file = Some(enabled);
+        self
+    }
+    pub fn include_line(mut self, enabled: bool) -> Self {
+        self.include_line = Some(enabled);
+        self
+    }
+    pub fn include_thread_name(mut self, enabled: bool) -> Self {
+        self.include_thread_name = Some(enabled);
+        self
+    }
+    pub fn include_thread_id(mut self, enabled: bool) -> Self {
+        self.include_thread_id = Some(enabled);
+        self
+    }
     pub fn span(mut self, enabled: bool) -> Self {
         self.span = Some(enabled);
         self
     }
+    pub fn flatten_span_fields(mut self, enabled: bool) -> Self {
+        self.flatten_span_fields = Some(enabled);
+        self
+    }
+    pub fn span_path(mut self, enabled: bool) -> Self {
+        self.span_path = Some(enabled);
+        self
+    }
     pub fn error_report(mut self, enabled: bool) -> Self {
         self.error_report = Some(enabled);
         self
     }
+    pub fn report_hook(mut self, hook: ReportHook) -> Self {
+        self.report_hook = Some(hook);
+        self
+    }
+    #[cfg(feature = "log")]
+    pub fn log_bridge(mut self, enabled: bool) -> Self {
+        self.log_bridge = Some(enabled);
+        self
+    }
+    #[cfg(feature = "signal")]
+    pub fn signal_verbosity(mut self, enabled: bool) -> Self {
+        self.signal_verbosity = Some(enabled);
+        self
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -132,11 +379,29 @@ pub struct Config {
     pub quiet: u8,
     pub filter_directives: Option<Cow<'static, str>>,
     pub base_fields: Vec<(Cow<'static, str>, Value)>,
+    pub outputs: Vec<Output>,
+    pub non_blocking: Option<NonBlocking>,
+    pub time_style: TimeStyle,
+    pub timestamp_format: TimestampFormat,
+    pub timestamp_key: Cow<'static, str>,
     pub include_pid: bool,
     pub include_exe: bool,
     pub include_version: bool,
+    pub include_target: bool,
+    pub include_module_path: bool,
+    pub include_file: bool,
+    pub include_line: bool,
+    pub include_thread_name: bool,
+    pub include_thread_id: bool,
     pub span: bool,
+    pub flatten_span_fields: bool,
+    pub span_path: bool,
     pub error_report: bool,
+    pub report_hook: ReportHook,
+    #[cfg(feature = "log")]
+    pub log_bridge: bool,
+    #[cfg(feature = "signal")]
+    pub signal_verbosity: bool,
 }
 
 impl Config {
@@ -151,11 +416,29 @@ impl Config {
             quiet: 0,
             filter_directives: None,
             base_fields: Vec::new(),
+            outputs: Vec::new(),
+            non_blocking: None,
+            time_style: TimeStyle::None,
+            timestamp_format: TimestampFormat::UnixMillis,
+            timestamp_key: Cow::Borrowed("timestamp"),
             include_pid: false,
             include_exe: false,
             include_version: false,
+            include_target: false,
+            include_module_path: false,
+            include_file: false,
+            include_line: false,
+            include_thread_name: false,
+            include_thread_id: false,
             span: false,
+            flatten_span_fields: false,
+            span_path: false,
             error_report: true,
+            report_hook: ReportHook::new(),
+            #[cfg(feature = "log")]
+            log_bridge: true,
+            #[cfg(feature = "signal")]
+            signal_verbosity: false,
         }
     }
     pub fn from_argv0() -> Self {
@@ -181,6 +464,10 @@ impl Config {
         self.output_format = OutputFormat::Json;
         self
     }
+    pub fn logfmt(mut self) -> Self {
+        self.output_format = OutputFormat::Logfmt;
+        self
+    }
     pub fn stream(mut self, stream: Stream) -> Self {
         self.stream = stream;
         self
@@ -209,6 +496,31 @@ impl Config {
         self.base_fields.push((key.into(), value.into()));
         self
     }
+    pub fn with_output(mut self, output: Output) -> Self {
+        self.outputs.push(output);
+        self
+    }
+    /// Queues formatted lines for a background writer thread. Syslog and
+    /// journald outputs keep writing inline because they need per-event
+    /// metadata. Use `try_init` and keep the returned `Handle` alive until
+    /// exit; dropping it flushes the queue. `init` discards the handle, so
+    /// lines still queued when the process exits are lost.
+    pub fn non_blocking(mut self, non_blocking: NonBlocking) -> Self {
+        self.non_blocking = Some(non_blocking);
+        self
+    }
+    pub fn time_style(mut self, time_style: TimeStyle) -> Self {
+        self.time_style = time_style;
+        self
+    }
+    pub fn timestamp_format(mut self, timestamp_format: TimestampFormat) -> Self {
+        self.timestamp_format = timestamp_format;
+        self
+    }
+    pub fn timestamp_key(mut self, key: impl Into<Cow<'static, str>>) -> Self {
+        self.timestamp_key = key.into();
+        self
+    }
     pub fn with_pid(mut self) -> Self {
         self.include_pid = true;
         self
@@ -221,12 +533,58 @@ impl Config {
         self.include_version = true;
         self
     }
+    pub fn with_target(mut self) -> Self {
+        self.include_target = true;
+        self
+    }
+    pub fn with_module_path(mut self) -> Self {
+        self.include_module_path = true;
+        self
+    }
+    pub fn with_file(mut self) -> Self {
+        self.include_file = true;
+        self
+    }
+    pub fn with_line(mut self) -> Self {
+        self.include_line = true;
+        self
+    }
+    pub fn with_thread_name(mut self) -> Self {
+        self.include_thread_name = true;
+        self
+    }
+    pub fn with_thread_id(mut self) -> Self {
+        self.include_thread_id = true;
+        self
+    }
     pub fn span(mut self, enabled: bool) -> Self {
         self.span = enabled;
         self
     }
+    pub fn flatten_span_fields(mut self, enabled: bool) -> Self {
+        self.flatten_span_fields = enabled;
+        self
+    }
+    pub fn span_path(mut self, enabled: bool) -> Self {
+        self.span_path = enabled;
+        self
+    }
     pub fn error_report(mut self, enabled: bool) -> Self {
         self.error_report = enabled;
         self
     }
+    pub fn report_hook(mut self, hook: ReportHook) -> Self {
+        self.report_hook = hook;
+        self
+    }
+    #[cfg(feature = "log")]
+    pub fn log_bridge(mut self, enabled: bool) -> Self {
+        self.log_bridge = enabled;
+        self
+    }
+    #[cfg(feature = "signal")]
+    pub fn signal_verbosity(mut self, enabled: bool) -> Self {
+        self.signal_verbosity = enabled;
+        self
+    }
 }
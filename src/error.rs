@@ -1,22 +1,54 @@
 #[derive(Debug)]
 pub enum InitError {
+    ConnectJournald(String),
+    ConnectSyslog(String),
     InvalidFilterDirectives(String),
     InstallErrorReporter(String),
+    InstallLogBridge(String),
+    InstallSignalHandler(String),
+    OpenLogFd(String),
+    OpenLogFile(String),
+    ReloadFilter(String),
     SetGlobalDefault(String),
+    SpawnWriter(String),
 }
 
 impl std::fmt::Display for InitError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            Self::ConnectJournald(details) => {
+                write!(f, "failed to connect to journald: {details}")
+            }
+            Self::ConnectSyslog(details) => {
+                write!(f, "failed to connect to syslog: {details}")
+            }
             Self::InvalidFilterDirectives(details) => {
                 write!(f, "invalid filter directives: {details}")
             }
             Self::InstallErrorReporter(details) => {
                 write!(f, "failed to install error reporter: {details}")
             }
+            Self::InstallLogBridge(details) => {
+                write!(f, "failed to install log bridge: {details}")
+            }
+            Self::InstallSignalHandler(details) => {
+                write!(f, "failed to install signal handler: {details}")
+            }
+            Self::OpenLogFd(details) => {
+                write!(f, "failed to open log file descriptor: {details}")
+            }
+            Self::OpenLogFile(details) => {
+                write!(f, "failed to open log file: {details}")
+            }
+            Self::ReloadFilter(details) => {
+                write!(f, "failed to reload filter: {details}")
+            }
             Self::SetGlobalDefault(details) => {
                 write!(f, "failed to set global tracing subscriber: {details}")
             }
+            Self::SpawnWriter(details) => {
+                write!(f, "failed to spawn background writer: {details}")
+            }
         }
     }
 }
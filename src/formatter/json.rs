@@ -1,3 +1,9 @@
+use super::{
+    MetadataFields, SpanFields, ThreadFields, chain_key, error_chain, is_log_field,
+    normalized_metadata, time::rfc3339_utc,
+};
+use crate::config::TimestampFormat;
+use crate::panic::is_panic_event;
 use serde_json::{Map, Number, Value};
 use std::{
     borrow::Cow,
@@ -13,6 +19,11 @@ pub struct JsonFormatter {
     pub name: Cow<'static, str>,
     pub base_fields: Vec<(String, Value)>,
     pub span: bool,
+    pub flatten_span_fields: bool,
+    pub metadata: MetadataFields,
+    pub thread: ThreadFields,
+    pub timestamp_format: TimestampFormat,
+    pub timestamp_key: Cow<'static, str>,
 }
 
 impl<S, N> FormatEvent<S, N> for JsonFormatter
@@ -26,81 +37,81 @@ where
         mut writer: Writer<'_>,
         event: &Event<'_>,
     ) -> std::fmt::Result {
+        let normalized = normalized_metadata(event);
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
         let mut map = Map::new();
-        map.insert("timestamp".to_string(), unix_timestamp_millis());
+        map.insert(
+            self.timestamp_key.to_string(),
+            timestamp(self.timestamp_format),
+        );
         map.insert(
             "level".to_string(),
-            Value::String(event.metadata().level().to_string()),
+            Value::String(metadata.level().to_string()),
         );
         map.insert("name".to_string(), Value::String(self.name.to_string()));
 
+        event.record(&mut JsonFieldVisitor {
+            map: &mut map,
+            log_record: normalized.is_some(),
+        });
+        if is_panic_event(metadata)
+            && let Some(Value::String(backtrace)) = map.remove("backtrace")
         {
-            struct JsonFieldVisitor<'a> {
-                map: &'a mut Map<String, Value>,
-            }
-
-            impl tracing_subscriber::field::Visit for JsonFieldVisitor<'_> {
-                fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
-                    self.map
-                        .insert(field.name().to_string(), Value::String(value.to_string()));
-                }
-
-                fn record_bool(&mut self, field: &tracing::field::Field, value: bool) {
-                    self.map
-                        .insert(field.name().to_string(), Value::Bool(value));
-                }
-
-                fn record_i64(&mut self, field: &tracing::field::Field, value: i64) {
-                    self.map
-                        .insert(field.name().to_string(), Value::Number(value.into()));
-                }
-
-                fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
-                    self.map
-                        .insert(field.name().to_string(), Value::Number(value.into()));
-                }
-
-                fn record_f64(&mut self, field: &tracing::field::Field, value: f64) {
-                    let json_value = Number::from_f64(value)
-                        .map(Value::Number)
-                        .unwrap_or_else(|| Value::String(value.to_string()));
-                    self.map.insert(field.name().to_string(), json_value);
-                }
-
-                fn record_error(
-                    &mut self,
-                    field: &tracing::field::Field,
-                    value: &(dyn std::error::Error + 'static),
-                ) {
-                    self.map
-                        .insert(field.name().to_string(), Value::String(value.to_string()));
-                }
-
-                fn record_debug(
-                    &mut self,
-                    field: &tracing::field::Field,
-                    value: &dyn std::fmt::Debug,
-                ) {
-                    self.map.insert(
-                        field.name().to_string(),
-                        Value::String(format!("{value:?}")),
-                    );
-                }
-            }
-
-            event.record(&mut JsonFieldVisitor { map: &mut map });
+            let frames = backtrace.lines().map(|frame| frame.into()).collect();
+            map.insert("backtrace".to_string(), Value::Array(frames));
         }
 
         for (key, value) in &self.base_fields {
             map.insert(key.clone(), value.clone());
         }
 
+        for (key, value) in self
+            .metadata
+            .collect(metadata)
+            .into_iter()
+            .chain(self.thread.collect())
+        {
+            map.insert(key.to_string(), value);
+        }
+
         if self.span
             && let Some(span) = ctx.lookup_current()
         {
             map.insert("span".to_string(), Value::String(span.name().to_string()));
         }
 
+        if (self.span || self.flatten_span_fields)
+            && let Some(scope) = ctx.event_scope()
+        {
+            let mut spans = Vec::new();
+            let mut flattened = Map::new();
+            for span in scope.from_root() {
+                let extensions = span.extensions();
+                let fields = extensions.get::<SpanFields>().map(|fields| &fields.0);
+
+                if self.span {
+                    let mut object = fields.cloned().unwrap_or_default();
+                    if let Some(name) = object.remove("name") {
+                        object.insert("span.name".to_string(), name);
+                    }
+                    object.insert("name".to_string(), Value::String(span.name().to_string()));
+                    spans.push(Value::Object(object));
+                }
+                if self.flatten_span_fields
+                    && let Some(fields) = fields
+                {
+                    flattened.extend(fields.clone());
+                }
+            }
+
+            if self.span {
+                map.insert("spans".to_string(), Value::Array(spans));
+            }
+            for (key, value) in flattened {
+                map.entry(key).or_insert(value);
+            }
+        }
+
         map.entry("message".to_string())
             .or_insert_with(|| Value::String(String::new()));
 
@@ -109,12 +120,82 @@ where
     }
 }
 
-fn unix_timestamp_millis() -> Value {
-    let millis = match SystemTime::now().duration_since(UNIX_EPOCH) {
-        Ok(duration) => duration.as_millis(),
-        Err(_) => 0,
-    };
+pub(crate) struct JsonFieldVisitor<'a> {
+    pub(crate) map: &'a mut Map<String, Value>,
+    pub(crate) log_record: bool,
+}
+
+impl JsonFieldVisitor<'_> {
+    fn skips(&self, field: &tracing::field::Field) -> bool {
+        self.log_record && is_log_field(field.name())
+    }
+
+    fn insert(&mut self, field: &tracing::field::Field, value: Value) {
+        if !self.skips(field) {
+            self.map.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+impl tracing_subscriber::field::Visit for JsonFieldVisitor<'_> {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.insert(field, Value::String(value.to_string()));
+    }
+
+    fn record_bool(&mut self, field: &tracing::field::Field, value: bool) {
+        self.insert(field, Value::Bool(value));
+    }
+
+    fn record_i64(&mut self, field: &tracing::field::Field, value: i64) {
+        self.insert(field, Value::Number(value.into()));
+    }
 
-    let millis = u64::try_from(millis).unwrap_or(u64::MAX);
-    Value::Number(millis.into())
+    fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+        self.insert(field, Value::Number(value.into()));
+    }
+
+    fn record_f64(&mut self, field: &tracing::field::Field, value: f64) {
+        let json_value = Number::from_f64(value)
+            .map(Value::Number)
+            .unwrap_or_else(|| Value::String(value.to_string()));
+        self.insert(field, json_value);
+    }
+
+    fn record_error(
+        &mut self,
+        field: &tracing::field::Field,
+        value: &(dyn std::error::Error + 'static),
+    ) {
+        self.insert(field, Value::String(value.to_string()));
+
+        let chain: Vec<Value> = error_chain(value).into_iter().map(Value::String).collect();
+        if !chain.is_empty() && !self.skips(field) {
+            self.map
+                .insert(chain_key(field.name()), Value::Array(chain));
+        }
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.insert(field, Value::String(format!("{value:?}")));
+    }
+}
+
+fn timestamp(format: TimestampFormat) -> Value {
+    let now = SystemTime::now();
+    let since_epoch = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+
+    match format {
+        TimestampFormat::UnixMillis => {
+            let millis = u64::try_from(since_epoch.as_millis()).unwrap_or(u64::MAX);
+            Value::Number(millis.into())
+        }
+        TimestampFormat::UnixSeconds => Number::from_f64(since_epoch.as_secs_f64())
+            .map(Value::Number)
+            .unwrap_or_else(|| Value::Number(0.into())),
+        TimestampFormat::UnixNanos => {
+            let nanos = u64::try_from(since_epoch.as_nanos()).unwrap_or(u64::MAX);
+            Value::Number(nanos.into())
+        }
+        TimestampFormat::Rfc3339 => Value::String(rfc3339_utc(now, 9)),
+    }
 }
@@ -0,0 +1,182 @@
+use super::{
+    MetadataFields, ThreadFields, chain_key, error_chain, is_log_field, normalized_metadata,
+    time::rfc3339_utc,
+};
+use serde_json::Value;
+use std::{borrow::Cow, fmt::Write as _, time::SystemTime};
+use tracing::Event;
+use tracing_subscriber::{
+    fmt::{FmtContext, FormatEvent, FormatFields, format::Writer},
+    registry::LookupSpan,
+};
+
+pub struct LogfmtFormatter {
+    pub name: Cow<'static, str>,
+    pub base_fields: Vec<(String, Value)>,
+    pub span: bool,
+    pub metadata: MetadataFields,
+    pub thread: ThreadFields,
+}
+
+impl<S, N> FormatEvent<S, N> for LogfmtFormatter
+where
+    S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> std::fmt::Result {
+        let normalized = normalized_metadata(event);
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+        let level = metadata.level().as_str().to_ascii_lowercase();
+        write!(writer, "ts={}", rfc3339_utc(SystemTime::now(), 3))?;
+        write!(writer, " level={level}")?;
+        write_pair(&mut writer, "name", &self.name)?;
+
+        let mut visitor = LogfmtFieldVisitor {
+            log_record: normalized.is_some(),
+            ..LogfmtFieldVisitor::default()
+        };
+        event.record(&mut visitor);
+        write_pair(&mut writer, "msg", visitor.message.as_deref().unwrap_or(""))?;
+
+        for (key, value) in &visitor.fields {
+            write_pair(&mut writer, key, value)?;
+        }
+
+        for (key, value) in &self.base_fields {
+            match value {
+                Value::String(value) => write_pair(&mut writer, key, value)?,
+                value => write_pair(&mut writer, key, &value.to_string())?,
+            }
+        }
+
+        for (key, value) in self
+            .metadata
+            .collect(metadata)
+            .into_iter()
+            .chain(self.thread.collect())
+        {
+            match value {
+                Value::String(value) => write_pair(&mut writer, key, &value)?,
+                value => write_pair(&mut writer, key, &value.to_string())?,
+            }
+        }
+
+        if self.span
+            && let Some(span) = ctx.lookup_current()
+        {
+            write_pair(&mut writer, "span", span.name())?;
+        }
+
+        writeln!(writer)
+    }
+}
+
+#[derive(Default)]
+struct LogfmtFieldVisitor {
+    message: Option<String>,
+    fields: Vec<(String, String)>,
+    log_record: bool,
+}
+
+impl LogfmtFieldVisitor {
+    fn skips(&self, field: &tracing::field::Field) -> bool {
+        self.log_record && is_log_field(field.name())
+    }
+
+    fn push(&mut self, field: &tracing::field::Field, value: String) {
+        if self.skips(field) {
+            return;
+        }
+        if field.name() == "message" {
+            self.message = Some(value);
+        } else {
+            self.fields.push((field.name().to_string(), value));
+        }
+    }
+}
+
+impl tracing_subscriber::field::Visit for LogfmtFieldVisitor {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.push(field, value.to_string());
+    }
+
+    fn record_error(
+        &mut self,
+        field: &tracing::field::Field,
+        value: &(dyn std::error::Error + 'static),
+    ) {
+        self.push(field, value.to_string());
+
+        let chain = error_chain(value);
+        if !chain.is_empty() && !self.skips(field) {
+            self.fields
+                .push((chain_key(field.name()), format!("[{}]", chain.join(", "))));
+        }
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.push(field, format!("{value:?}"));
+    }
+}
+
+fn write_pair(w: &mut Writer<'_>, key: &str, value: &str) -> std::fmt::Result {
+    write!(w, " {key}=")?;
+    if !needs_quoting(value) {
+        return w.write_str(value);
+    }
+
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            ch if ch.is_control() => {
+                let _ = write!(quoted, "\\u{:04x}", ch as u32);
+            }
+            ch => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    w.write_str(&quoted)
+}
+
+fn needs_quoting(value: &str) -> bool {
+    value.is_empty()
+        || value
+            .chars()
+            .any(|ch| ch == ' ' || ch == '=' || ch == '"' || ch == '\\' || ch.is_control())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(key: &str, value: &str) -> String {
+        let mut out = String::new();
+        write_pair(&mut Writer::new(&mut out), key, value).unwrap();
+        out
+    }
+
+    #[test]
+    fn quotes_and_escapes_values_only_when_needed() {
+        assert_eq!(pair("user", "alice"), " user=alice");
+        assert_eq!(pair("msg", ""), " msg=\"\"");
+        assert_eq!(pair("msg", "hello world"), " msg=\"hello world\"");
+        assert_eq!(pair("q", "a=b"), " q=\"a=b\"");
+        assert_eq!(pair("q", "say \"hi\"\n"), " q=\"say \\\"hi\\\"\\n\"");
+        assert_eq!(pair("path", "C:\\tmp"), " path=\"C:\\\\tmp\"");
+        assert_eq!(
+            pair("msg", "\x1b[31mred\x1b[0m\0"),
+            " msg=\"\\u001b[31mred\\u001b[0m\\u0000\""
+        );
+    }
+}
@@ -1,18 +1,53 @@
 mod json;
+mod logfmt;
 mod plain;
+mod span;
+pub(crate) mod time;
 
-use tracing::Event;
+use serde_json::Value;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::{Event, Metadata};
 use tracing_subscriber::{
     fmt::{FmtContext, FormatEvent, FormatFields, format::Writer},
     registry::LookupSpan,
 };
 
+pub(crate) use json::JsonFieldVisitor;
 pub use json::JsonFormatter;
+pub use logfmt::LogfmtFormatter;
 pub use plain::PlainFormatter;
+pub(crate) use span::{SpanFields, SpanFieldsLayer};
+
+#[cfg(feature = "log")]
+pub(crate) fn normalized_metadata<'a>(event: &'a Event<'a>) -> Option<Metadata<'a>> {
+    use tracing_log::NormalizeEvent;
+    event.normalized_metadata()
+}
+
+#[cfg(not(feature = "log"))]
+pub(crate) fn normalized_metadata<'a>(_event: &'a Event<'a>) -> Option<Metadata<'a>> {
+    None
+}
+
+pub(crate) fn is_log_field(name: &str) -> bool {
+    name.starts_with("log.")
+}
+
+pub(crate) fn error_chain(err: &(dyn std::error::Error + 'static)) -> Vec<String> {
+    std::iter::successors(err.source(), |err| err.source())
+        .map(ToString::to_string)
+        .collect()
+}
+
+pub(crate) fn chain_key(name: &str) -> String {
+    let prefix = name.strip_suffix(".message").unwrap_or(name);
+    format!("{prefix}.chain")
+}
 
 pub enum Formatter {
     Plain(PlainFormatter),
     Json(JsonFormatter),
+    Logfmt(LogfmtFormatter),
 }
 
 impl<S, N> FormatEvent<S, N> for Formatter
@@ -29,6 +64,90 @@ where
         match self {
             Self::Plain(formatter) => formatter.format_event(ctx, writer, event),
             Self::Json(formatter) => formatter.format_event(ctx, writer, event),
+            Self::Logfmt(formatter) => formatter.format_event(ctx, writer, event),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MetadataFields {
+    pub target: bool,
+    pub module_path: bool,
+    pub file: bool,
+    pub line: bool,
+}
+
+impl MetadataFields {
+    pub(crate) fn collect(&self, metadata: &Metadata<'_>) -> Vec<(&'static str, Value)> {
+        let mut fields = Vec::new();
+        if self.target {
+            fields.push(("target", Value::String(metadata.target().to_string())));
+        }
+        if self.module_path
+            && let Some(module_path) = metadata.module_path()
+        {
+            fields.push(("module_path", Value::String(module_path.to_string())));
+        }
+        if self.file
+            && let Some(file) = metadata.file()
+        {
+            fields.push(("file", Value::String(file.to_string())));
         }
+        if self.line
+            && let Some(line) = metadata.line()
+        {
+            fields.push(("line", Value::Number(line.into())));
+        }
+        fields
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ThreadFields {
+    pub name: bool,
+    pub id: bool,
+}
+
+impl ThreadFields {
+    pub(crate) fn collect(&self) -> Vec<(&'static str, Value)> {
+        let mut fields = Vec::new();
+        if self.name {
+            fields.push(("thread_name", Value::String(current_thread_name())));
+        }
+        if self.id {
+            fields.push(("thread_id", Value::Number(current_thread_id().into())));
+        }
+        fields
+    }
+}
+
+pub(crate) fn current_thread_name() -> String {
+    std::thread::current()
+        .name()
+        .unwrap_or("<unnamed>")
+        .to_string()
+}
+
+pub(crate) fn current_thread_id() -> u64 {
+    static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+    thread_local! {
+        static THREAD_ID: u64 = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+    }
+    THREAD_ID.with(|id| *id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thread_ids_are_stable_per_thread_and_distinct_across_threads() {
+        let main = current_thread_id();
+        assert_eq!(main, current_thread_id());
+        assert_ne!(main, 0);
+
+        let other = std::thread::spawn(current_thread_id).join().unwrap();
+        assert_ne!(main, other);
+        assert_ne!(other, 0);
     }
 }
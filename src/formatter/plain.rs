@@ -1,7 +1,17 @@
-use serde_json::Value;
-use std::borrow::Cow;
+use super::{
+    MetadataFields, SpanFields, ThreadFields, chain_key, current_thread_id, current_thread_name,
+    error_chain, is_log_field, normalized_metadata,
+    time::{local_offset_secs, rfc3339, rfc3339_utc},
+};
+use crate::config::TimeStyle;
+use serde_json::{Map, Value};
+use std::{
+    borrow::Cow,
+    time::{Instant, SystemTime},
+};
 use tracing::{Event, Level};
 use tracing_subscriber::{
+    field::Visit,
     fmt::{FmtContext, FormatEvent, FormatFields, format::Writer},
     registry::LookupSpan,
 };
@@ -11,6 +21,11 @@ pub struct PlainFormatter {
     pub color: bool,
     pub base_fields: Vec<(String, Value)>,
     pub span: bool,
+    pub span_path: bool,
+    pub metadata: MetadataFields,
+    pub thread: ThreadFields,
+    pub time_style: TimeStyle,
+    pub start: Instant,
 }
 
 impl<S, N> FormatEvent<S, N> for PlainFormatter
@@ -24,15 +39,54 @@ where
         mut writer: Writer<'_>,
         event: &Event<'_>,
     ) -> std::fmt::Result {
+        let normalized = normalized_metadata(event);
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+        write_time(&mut writer, self.time_style, self.start)?;
         write!(writer, "[{}] ", self.name)?;
-        write_level(&mut writer, event.metadata().level(), self.color)?;
+        write_thread(&mut writer, self.thread)?;
+        write_level(&mut writer, metadata.level(), self.color)?;
         write!(writer, " ")?;
-        ctx.format_fields(writer.by_ref(), event)?;
+
+        if self.span_path
+            && let Some(scope) = ctx.event_scope()
+        {
+            for (index, span) in scope.from_root().enumerate() {
+                if index > 0 {
+                    write!(writer, ":")?;
+                }
+                write!(writer, "{}", span.name())?;
+
+                let extensions = span.extensions();
+                if let Some(SpanFields(fields)) = extensions.get::<SpanFields>()
+                    && !fields.is_empty()
+                {
+                    write_span_fields(&mut writer, fields, self.color)?;
+                }
+            }
+            write!(writer, " ")?;
+        }
+
+        let mut fields = PlainFieldVisitor {
+            writer: &mut writer,
+            color: self.color,
+            log_record: normalized.is_some(),
+            is_empty: true,
+            result: Ok(()),
+        };
+        event.record(&mut fields);
+        fields.result?;
 
         for (key, value) in &self.base_fields {
             write!(writer, " {key}={value}")?;
         }
 
+        for (key, value) in self.metadata.collect(metadata) {
+            match value {
+                Value::String(value) => write!(writer, " {key}={value}")?,
+                value => write!(writer, " {key}={value}")?,
+            }
+        }
+
         if self.span
             && let Some(span) = ctx.lookup_current()
         {
@@ -43,6 +97,61 @@ where
     }
 }
 
+fn write_time(w: &mut Writer<'_>, style: TimeStyle, start: Instant) -> std::fmt::Result {
+    match style {
+        TimeStyle::None => Ok(()),
+        TimeStyle::Local => {
+            let now = SystemTime::now();
+            match local_offset_secs(now) {
+                Some(offset) => write!(w, "{} ", rfc3339(now, offset, 3)),
+                None => write!(w, "{} ", rfc3339_utc(now, 3)),
+            }
+        }
+        TimeStyle::Utc => write!(w, "{} ", rfc3339_utc(SystemTime::now(), 3)),
+        TimeStyle::Uptime => {
+            let elapsed = start.elapsed();
+            write!(
+                w,
+                "{:>4}.{:06}s ",
+                elapsed.as_secs(),
+                elapsed.subsec_micros()
+            )
+        }
+    }
+}
+
+fn write_span_fields(
+    w: &mut Writer<'_>,
+    fields: &Map<String, Value>,
+    color: bool,
+) -> std::fmt::Result {
+    write!(w, "{{")?;
+    for (index, (key, value)) in fields.iter().enumerate() {
+        if index > 0 {
+            write!(w, " ")?;
+        }
+        if color {
+            write!(w, "\x1b[3m{key}\x1b[0m\x1b[2m=\x1b[0m")?;
+        } else {
+            write!(w, "{key}=")?;
+        }
+        match value {
+            Value::String(value) => write!(w, "{value:?}")?,
+            value => write!(w, "{value}")?,
+        }
+    }
+    write!(w, "}}")
+}
+
+fn write_thread(w: &mut Writer<'_>, thread: ThreadFields) -> std::fmt::Result {
+    match (thread.name, thread.id) {
+        (false, false) => Ok(()),
+        (true, false) => write!(w, "[{}] ", current_thread_name()),
+        (false, true) => write!(w, "[#{}] ", current_thread_id()),
+        (true, true) => write!(w, "[{}#{}] ", current_thread_name(), current_thread_id()),
+    }
+}
+
 fn write_level(w: &mut Writer<'_>, level: &Level, color: bool) -> std::fmt::Result {
     if !color {
         return write!(w, "{level}");
@@ -58,3 +167,61 @@ fn write_level(w: &mut Writer<'_>, level: &Level, color: bool) -> std::fmt::Resu
 
     write!(w, "\x1b[{code}m{level_name}\x1b[0m")
 }
+
+struct PlainFieldVisitor<'a, 'w> {
+    writer: &'a mut Writer<'w>,
+    color: bool,
+    log_record: bool,
+    is_empty: bool,
+    result: std::fmt::Result,
+}
+
+impl PlainFieldVisitor<'_, '_> {
+    fn write_field(&mut self, name: &str, value: &dyn std::fmt::Debug) {
+        if self.result.is_err() || (self.log_record && is_log_field(name)) {
+            return;
+        }
+        if !std::mem::take(&mut self.is_empty) {
+            self.result = write!(self.writer, " ");
+        }
+        let name = name.strip_prefix("r#").unwrap_or(name);
+        self.result = self.result.and_then(|()| match name {
+            "message" => {
+                let message = format!("{value:?}").replace('\x1b', "\\x1b");
+                self.writer.write_str(&message)
+            }
+            name if self.color => {
+                write!(self.writer, "\x1b[3m{name}\x1b[0m\x1b[2m=\x1b[0m{value:?}")
+            }
+            name => write!(self.writer, "{name}={value:?}"),
+        });
+    }
+}
+
+impl Visit for PlainFieldVisitor<'_, '_> {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        if field.name() == "message" {
+            self.write_field(field.name(), &format_args!("{value}"));
+        } else {
+            self.write_field(field.name(), &value);
+        }
+    }
+
+    fn record_error(
+        &mut self,
+        field: &tracing::field::Field,
+        value: &(dyn std::error::Error + 'static),
+    ) {
+        self.write_field(field.name(), &format_args!("{value}"));
+
+        let chain = error_chain(value);
+        if !chain.is_empty() {
+            let key = chain_key(field.name());
+            self.write_field(&key, &format_args!("[{}]", chain.join(", ")));
+        }
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.write_field(field.name(), value);
+    }
+}
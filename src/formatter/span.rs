@@ -0,0 +1,46 @@
+use super::JsonFieldVisitor;
+use serde_json::{Map, Value};
+use tracing::{
+    Subscriber,
+    span::{Attributes, Id, Record},
+};
+use tracing_subscriber::{Layer, layer::Context, registry::LookupSpan};
+
+pub(crate) struct SpanFields(pub(crate) Map<String, Value>);
+
+pub(crate) struct SpanFieldsLayer;
+
+impl<S> Layer<S> for SpanFieldsLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        if extensions.get_mut::<SpanFields>().is_some() {
+            return;
+        }
+
+        let mut map = Map::new();
+        attrs.record(&mut JsonFieldVisitor {
+            map: &mut map,
+            log_record: false,
+        });
+        extensions.insert(SpanFields(map));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        if let Some(SpanFields(map)) = extensions.get_mut::<SpanFields>() {
+            values.record(&mut JsonFieldVisitor {
+                map,
+                log_record: false,
+            });
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub(crate) fn rfc3339_utc(time: SystemTime, subsec_digits: u32) -> String {
+    rfc3339(time, 0, subsec_digits)
+}
+
+pub(crate) fn rfc3339(time: SystemTime, offset_secs: i64, subsec_digits: u32) -> String {
+    let (secs, nanos) = match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => (duration.as_secs() as i64, duration.subsec_nanos()),
+        Err(_) => (0, 0),
+    };
+    let local = secs + offset_secs;
+    let (year, month, day) = civil_from_days(local.div_euclid(86_400));
+    let secs_of_day = local.rem_euclid(86_400);
+
+    let mut out = format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60
+    );
+    if subsec_digits > 0 {
+        let digits = subsec_digits.min(9);
+        let fraction = nanos / 10u32.pow(9 - digits);
+        out.push_str(&format!(".{fraction:0width$}", width = digits as usize));
+    }
+    if offset_secs == 0 {
+        out.push('Z');
+    } else {
+        let sign = if offset_secs < 0 { '-' } else { '+' };
+        let offset = offset_secs.abs();
+        out.push_str(&format!(
+            "{sign}{:02}:{:02}",
+            offset / 3600,
+            offset % 3600 / 60
+        ));
+    }
+    out
+}
+
+#[cfg(unix)]
+pub(crate) fn bsd_timestamp(time: SystemTime, offset_secs: i64) -> String {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let secs = match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        Err(_) => 0,
+    };
+    let local = secs + offset_secs;
+    let (_, month, day) = civil_from_days(local.div_euclid(86_400));
+    let secs_of_day = local.rem_euclid(86_400);
+    format!(
+        "{} {day:>2} {:02}:{:02}:{:02}",
+        MONTHS[month as usize - 1],
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60
+    )
+}
+
+// Returns `None` when the local offset cannot be determined; callers fall
+// back to UTC. Non-Unix targets always take that fallback.
+#[cfg(unix)]
+pub(crate) fn local_offset_secs(time: SystemTime) -> Option<i64> {
+    let secs = match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => libc::time_t::try_from(duration.as_secs()).ok()?,
+        Err(_) => 0,
+    };
+    // SAFETY: `libc::tm` is a plain C struct for which all-zero bytes are a
+    // valid value.
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    // SAFETY: both pointers refer to live, properly aligned locals. The
+    // reentrant variant writes only into `tm`. It does read `TZ` and the
+    // tz database, so a concurrent `setenv`/`tzset` from another thread is
+    // a data race in libc; we never modify the environment ourselves.
+    if unsafe { libc::localtime_r(&secs, &mut tm) }.is_null() {
+        return None;
+    }
+    Some(tm.tm_gmtoff as i64)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn local_offset_secs(_time: SystemTime) -> Option<i64> {
+    None
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn formats_rfc3339_in_utc_and_with_offset() {
+        let time = UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789);
+
+        assert_eq!(rfc3339_utc(time, 3), "2023-11-14T22:13:20.123Z");
+        assert_eq!(rfc3339_utc(time, 9), "2023-11-14T22:13:20.123456789Z");
+        assert_eq!(rfc3339_utc(UNIX_EPOCH, 0), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339(time, 9 * 3600, 0), "2023-11-15T07:13:20+09:00");
+        assert_eq!(
+            rfc3339(time, -5 * 3600 - 1800, 0),
+            "2023-11-14T16:43:20-05:30"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn formats_bsd_syslog_timestamp() {
+        let time = UNIX_EPOCH + Duration::new(1_700_000_000, 0);
+
+        assert_eq!(bsd_timestamp(time, 0), "Nov 14 22:13:20");
+        assert_eq!(bsd_timestamp(UNIX_EPOCH, 0), "Jan  1 00:00:00");
+    }
+
+    #[test]
+    fn civil_from_days_handles_leap_years() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+}
@@ -0,0 +1,405 @@
+use crate::error::InitError;
+use crate::init::level_filter;
+use crate::writer::Worker;
+use std::{
+    borrow::Cow,
+    sync::{
+        Arc, Mutex, MutexGuard,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use tracing::Subscriber;
+use tracing_subscriber::{EnvFilter, filter::LevelFilter, reload};
+
+type Reloader = Box<dyn Fn(EnvFilter) -> Result<(), reload::Error> + Send + Sync>;
+
+#[derive(Clone)]
+pub struct Handle {
+    inner: Arc<Inner>,
+    _flush_on_drop: Option<Arc<FlushGuard>>,
+}
+
+struct Inner {
+    state: Mutex<FilterState>,
+    baseline: FilterState,
+    reloaders: Vec<Reloader>,
+    workers: Vec<Worker>,
+    dropped: Vec<Arc<AtomicU64>>,
+}
+
+#[derive(Clone, Debug)]
+struct FilterState {
+    verbose: u8,
+    quiet: u8,
+    filter_directives: Option<Cow<'static, str>>,
+}
+
+impl Handle {
+    pub fn verbose(&self) -> u8 {
+        self.state().verbose
+    }
+
+    pub fn quiet(&self) -> u8 {
+        self.state().quiet
+    }
+
+    pub fn filter_directives(&self) -> Option<Cow<'static, str>> {
+        self.state().filter_directives.clone()
+    }
+
+    pub fn set_verbose(&self, verbose: u8) -> Result<(), InitError> {
+        self.update(FilterState {
+            verbose: verbose.min(2),
+            quiet: 0,
+            filter_directives: None,
+        })
+    }
+
+    pub fn set_quiet(&self, quiet: u8) -> Result<(), InitError> {
+        self.update(FilterState {
+            verbose: 0,
+            quiet: quiet.min(2),
+            filter_directives: None,
+        })
+    }
+
+    pub fn set_filter_directives(
+        &self,
+        directives: impl Into<Cow<'static, str>>,
+    ) -> Result<(), InitError> {
+        let state = self.state().clone();
+        self.update(FilterState {
+            filter_directives: Some(directives.into()),
+            ..state
+        })
+    }
+
+    pub fn flush(&self) {
+        self.inner.flush();
+    }
+
+    pub fn flush_guard(&self) -> FlushGuard {
+        FlushGuard {
+            inner: self.inner.clone(),
+        }
+    }
+
+    pub fn dropped_events(&self) -> u64 {
+        let queued: u64 = self.inner.workers.iter().map(Worker::dropped).sum();
+        let unsent: u64 = self
+            .inner
+            .dropped
+            .iter()
+            .map(|dropped| dropped.load(Ordering::Relaxed))
+            .sum();
+        queued + unsent
+    }
+
+    pub fn increase_verbosity(&self) -> Result<&'static str, InitError> {
+        self.step_verbosity(1, |_| {})
+    }
+
+    pub fn decrease_verbosity(&self) -> Result<&'static str, InitError> {
+        self.step_verbosity(-1, |_| {})
+    }
+
+    pub(crate) fn step_verbosity(
+        &self,
+        delta: i8,
+        announce: impl FnOnce(&'static str),
+    ) -> Result<&'static str, InitError> {
+        let state = self.state().clone();
+        let floor = self.inner.baseline.level();
+        let level = (state.level() + delta).clamp(floor, 2);
+        let next = if level == floor {
+            self.inner.baseline.clone()
+        } else {
+            state.with_level(level)
+        };
+        let name = level_name(level);
+        if delta < 0 {
+            announce(name);
+            self.update(next)?;
+        } else {
+            self.update(next)?;
+            announce(name);
+        }
+        Ok(name)
+    }
+
+    fn update(&self, next: FilterState) -> Result<(), InitError> {
+        let mut state = self.state();
+        let filters = self.filters(&next)?;
+        for (index, (reload, filter)) in self.inner.reloaders.iter().zip(filters).enumerate() {
+            if let Err(err) = reload(filter) {
+                if let Ok(previous) = self.filters(&state) {
+                    for (reload, filter) in self.inner.reloaders[..index].iter().zip(previous) {
+                        let _ = reload(filter);
+                    }
+                }
+                return Err(InitError::ReloadFilter(err.to_string()));
+            }
+        }
+        *state = next;
+        Ok(())
+    }
+
+    fn filters(&self, state: &FilterState) -> Result<Vec<EnvFilter>, InitError> {
+        let directives = state.directives();
+        let filter = EnvFilter::try_new(directives.as_ref())
+            .map_err(|err| InitError::InvalidFilterDirectives(err.to_string()))?;
+        let mut filters = Vec::with_capacity(self.inner.reloaders.len());
+        filters.push(filter);
+        while filters.len() < self.inner.reloaders.len() {
+            filters.push(EnvFilter::new(directives.as_ref()));
+        }
+        Ok(filters)
+    }
+
+    pub(crate) fn detached(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _flush_on_drop: None,
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, FilterState> {
+        self.inner
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Inner {
+    fn flush(&self) {
+        for worker in &self.workers {
+            worker.flush();
+        }
+    }
+}
+
+impl FilterState {
+    fn level(&self) -> i8 {
+        let bare = self.filter_directives.as_deref().and_then(|directives| {
+            directives
+                .split(',')
+                .find_map(|directive| directive.trim().parse::<LevelFilter>().ok())
+        });
+        match bare {
+            Some(LevelFilter::OFF) => -3,
+            Some(LevelFilter::ERROR) => -2,
+            Some(LevelFilter::WARN) => -1,
+            Some(LevelFilter::INFO) => 0,
+            Some(LevelFilter::DEBUG) => 1,
+            Some(_) => 2,
+            None => self.verbose.min(2) as i8 - self.quiet.min(2) as i8,
+        }
+    }
+
+    fn with_level(&self, level: i8) -> Self {
+        let verbose = level.max(0).unsigned_abs();
+        let quiet = level.min(0).unsigned_abs();
+        let filter_directives = self.filter_directives.as_deref().map(|directives| {
+            let targets = directives.split(',').map(str::trim).filter(|directive| {
+                !directive.is_empty() && directive.parse::<LevelFilter>().is_err()
+            });
+            let directives: Vec<&str> = std::iter::once(level_filter(verbose, quiet))
+                .chain(targets)
+                .collect();
+            Cow::Owned(directives.join(","))
+        });
+        Self {
+            verbose,
+            quiet,
+            filter_directives,
+        }
+    }
+
+    fn directives(&self) -> Cow<'static, str> {
+        match &self.filter_directives {
+            Some(directives) => directives.clone(),
+            None => Cow::Borrowed(level_filter(self.verbose, self.quiet)),
+        }
+    }
+}
+
+fn level_name(level: i8) -> &'static str {
+    match level {
+        ..=-3 => "off",
+        level => level_filter(level.max(0).unsigned_abs(), level.min(0).unsigned_abs()),
+    }
+}
+
+#[must_use = "dropping the guard flushes immediately"]
+pub struct FlushGuard {
+    inner: Arc<Inner>,
+}
+
+impl Drop for FlushGuard {
+    fn drop(&mut self) {
+        self.inner.flush();
+    }
+}
+
+impl std::fmt::Debug for FlushGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FlushGuard").finish_non_exhaustive()
+    }
+}
+
+impl std::fmt::Debug for Handle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.state();
+        f.debug_struct("Handle")
+            .field("verbose", &state.verbose)
+            .field("quiet", &state.quiet)
+            .field("filter_directives", &state.filter_directives)
+            .finish_non_exhaustive()
+    }
+}
+
+pub(crate) struct HandleBuilder {
+    state: FilterState,
+    reloaders: Vec<Reloader>,
+    workers: Vec<Worker>,
+    dropped: Vec<Arc<AtomicU64>>,
+}
+
+impl HandleBuilder {
+    pub(crate) fn new(
+        verbose: u8,
+        quiet: u8,
+        filter_directives: Option<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            state: FilterState {
+                verbose,
+                quiet,
+                filter_directives,
+            },
+            reloaders: Vec::new(),
+            workers: Vec::new(),
+            dropped: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push<S: Subscriber>(&mut self, handle: reload::Handle<EnvFilter, S>) {
+        self.reloaders
+            .push(Box::new(move |filter| handle.reload(filter)));
+    }
+
+    pub(crate) fn push_worker(&mut self, worker: Worker) {
+        self.workers.push(worker);
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn push_dropped(&mut self, dropped: Arc<AtomicU64>) {
+        self.dropped.push(dropped);
+    }
+
+    pub(crate) fn build(self) -> Handle {
+        let inner = Arc::new(Inner {
+            baseline: self.state.clone(),
+            state: Mutex::new(self.state),
+            reloaders: self.reloaders,
+            workers: self.workers,
+            dropped: self.dropped,
+        });
+        let flush_on_drop = (!inner.workers.is_empty()).then(|| {
+            Arc::new(FlushGuard {
+                inner: inner.clone(),
+            })
+        });
+        Handle {
+            inner,
+            _flush_on_drop: flush_on_drop,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_reload_rolls_back_earlier_outputs() {
+        let applied = Arc::new(Mutex::new(Vec::new()));
+        let seen = applied.clone();
+        let (layer, gone) =
+            reload::Layer::<EnvFilter, tracing_subscriber::Registry>::new(EnvFilter::new("info"));
+        drop(layer);
+        let reloaders: Vec<Reloader> = vec![
+            Box::new(move |filter: EnvFilter| {
+                seen.lock().unwrap().push(filter.to_string());
+                Ok(())
+            }),
+            Box::new(move |filter| gone.reload(filter)),
+        ];
+        let handle = Handle {
+            inner: Arc::new(Inner {
+                state: Mutex::new(FilterState {
+                    verbose: 0,
+                    quiet: 0,
+                    filter_directives: None,
+                }),
+                baseline: FilterState {
+                    verbose: 0,
+                    quiet: 0,
+                    filter_directives: None,
+                },
+                reloaders,
+                workers: Vec::new(),
+                dropped: Vec::new(),
+            }),
+            _flush_on_drop: None,
+        };
+
+        assert!(matches!(
+            handle.set_verbose(1),
+            Err(InitError::ReloadFilter(_))
+        ));
+        assert_eq!(handle.verbose(), 0);
+        assert_eq!(*applied.lock().unwrap(), ["debug", "info"]);
+    }
+
+    #[test]
+    fn stepping_keeps_target_directives_and_stops_at_baseline() {
+        let handle = HandleBuilder::new(0, 0, Some("myapp=trace,warn".into())).build();
+
+        assert_eq!(handle.decrease_verbosity().unwrap(), "warn");
+        assert_eq!(
+            handle.filter_directives().as_deref(),
+            Some("myapp=trace,warn")
+        );
+
+        assert_eq!(handle.increase_verbosity().unwrap(), "info");
+        assert_eq!(
+            handle.filter_directives().as_deref(),
+            Some("info,myapp=trace")
+        );
+        assert_eq!(handle.increase_verbosity().unwrap(), "debug");
+        assert_eq!(
+            handle.filter_directives().as_deref(),
+            Some("debug,myapp=trace")
+        );
+
+        assert_eq!(handle.decrease_verbosity().unwrap(), "info");
+        assert_eq!(handle.decrease_verbosity().unwrap(), "warn");
+        assert_eq!(
+            handle.filter_directives().as_deref(),
+            Some("myapp=trace,warn")
+        );
+    }
+
+    #[test]
+    fn stepping_without_directives_uses_verbose_and_quiet() {
+        let handle = HandleBuilder::new(0, 1, None).build();
+
+        assert_eq!(handle.increase_verbosity().unwrap(), "info");
+        assert_eq!((handle.verbose(), handle.quiet()), (0, 0));
+        assert_eq!(handle.decrease_verbosity().unwrap(), "warn");
+        assert_eq!(handle.decrease_verbosity().unwrap(), "warn");
+        assert_eq!((handle.verbose(), handle.quiet()), (0, 1));
+    }
+}
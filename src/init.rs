@@ -1,79 +1,276 @@
-use crate::config::{Color, Config, ConfigOverride, OutputFormat, Stream};
+use crate::config::{Color, Config, ConfigOverride, Output, OutputFormat, ReportTheme};
 use crate::error::InitError;
-use crate::formatter::{Formatter, JsonFormatter, PlainFormatter};
+use crate::formatter::{
+    Formatter, JsonFormatter, LogfmtFormatter, MetadataFields, PlainFormatter, SpanFieldsLayer,
+    ThreadFields,
+};
+use crate::handle::{Handle, HandleBuilder};
+#[cfg(unix)]
+use crate::writer::JournaldLayer;
+use crate::writer::{NonBlockingWriter, Sink, SinkKind, is_tty, sinks};
+use color_eyre::config::{HookBuilder, Theme};
 use serde_json::Value;
-use std::{collections::BTreeMap, io::IsTerminal, sync::OnceLock};
-use tracing_subscriber::{EnvFilter, fmt::time::SystemTime};
+use std::{borrow::Cow, collections::BTreeMap, sync::OnceLock, time::Instant};
+use tracing::{Subscriber, subscriber::DefaultGuard};
+use tracing_error::ErrorLayer;
+use tracing_subscriber::{
+    EnvFilter, Layer,
+    filter::{FilterExt, LevelFilter, filter_fn},
+    fmt::writer::BoxMakeWriter,
+    layer::{Filter, SubscriberExt},
+    registry::LookupSpan,
+    reload,
+};
 
-static INIT: OnceLock<()> = OnceLock::new();
+static INIT: OnceLock<Handle> = OnceLock::new();
 
 pub fn init(cfg: Config) {
     let _ = try_init(cfg);
 }
 
-pub fn try_init(cfg: Config) -> Result<(), InitError> {
-    if INIT.get().is_some() {
-        return Ok(());
+pub fn try_init(cfg: Config) -> Result<Handle, InitError> {
+    if let Some(handle) = INIT.get() {
+        return Ok(handle.clone());
     }
 
     let cfg = resolve_config(cfg);
 
+    let json_panics = matches!(cfg.output_format, OutputFormat::Json)
+        || cfg
+            .outputs
+            .iter()
+            .any(|output| matches!(output.output_format, OutputFormat::Json));
+    let mut panic_hook = None;
+    if cfg.error_report && json_panics {
+        let (hook, eyre_hook) = report_hook_builder(&cfg).into_hooks();
+        eyre_hook
+            .install()
+            .map_err(|err| InitError::InstallErrorReporter(err.to_string()))?;
+        panic_hook = Some(hook.into_panic_hook());
+    } else if cfg.error_report {
+        report_hook_builder(&cfg)
+            .install()
+            .map_err(|err| InitError::InstallErrorReporter(err.to_string()))?;
+    }
+
+    let (layers, handle) = build_layers(&cfg, true)?;
+    let subscriber = tracing_subscriber::registry().with(layers);
+    tracing::subscriber::set_global_default(subscriber)
+        .map_err(|err| InitError::SetGlobalDefault(err.to_string()))?;
+    INIT.get_or_init(|| handle.detached());
+    #[cfg(all(feature = "signal", unix))]
+    if cfg.signal_verbosity {
+        crate::signal::spawn(handle.detached())?;
+    }
+    #[cfg(feature = "log")]
+    if cfg.log_bridge {
+        tracing_log::LogTracer::init()
+            .map_err(|err| InitError::InstallLogBridge(err.to_string()))?;
+    }
+
+    if json_panics {
+        let chain = panic_hook.unwrap_or_else(std::panic::take_hook);
+        crate::panic::install(is_tty(&cfg.stream).then_some(chain));
+    }
+
+    Ok(handle)
+}
+
+pub fn set_default(cfg: Config) -> Result<(DefaultGuard, Handle), InitError> {
+    let cfg = resolve_config(cfg);
+    let (layers, handle) = build_layers(&cfg, true)?;
+    let subscriber = tracing_subscriber::registry().with(layers);
+    Ok((tracing::subscriber::set_default(subscriber), handle))
+}
+
+pub fn layer<S>(cfg: Config) -> Result<(impl Layer<S> + Send + Sync, Handle), InitError>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    build_layers(&resolve_config(cfg), true)
+}
+
+pub fn is_initialized() -> bool {
+    INIT.get().is_some()
+}
+
+type Layers<S> = Vec<Box<dyn Layer<S> + Send + Sync>>;
+
+pub(crate) fn build_layers<S>(
+    cfg: &Config,
+    respect_env: bool,
+) -> Result<(Layers<S>, Handle), InitError>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let base_fields = build_base_fields(cfg);
+    let primary = Output {
+        output_format: cfg.output_format,
+        stream: cfg.stream.clone(),
+        color: cfg.color,
+        filter_directives: None,
+    };
+    let directives = env_directives(respect_env)
+        .map(Cow::Owned)
+        .or_else(|| cfg.filter_directives.clone());
+    let mut handle = HandleBuilder::new(cfg.verbose, cfg.quiet, directives);
+
+    let mut layers = Vec::with_capacity(cfg.outputs.len() + 3);
+    if cfg.span || cfg.flatten_span_fields || cfg.span_path {
+        layers.push(SpanFieldsLayer.boxed());
+    }
     if cfg.error_report {
-        color_eyre::install().map_err(|err| InitError::InstallErrorReporter(err.to_string()))?;
+        layers.push(ErrorLayer::default().boxed());
     }
+    for output in std::iter::once(&primary).chain(&cfg.outputs) {
+        for sink in sinks(&cfg.name, &output.stream)? {
+            let route = sink.route;
+            let route_filter = filter_fn(move |metadata| route.allows(*metadata.level()))
+                .with_max_level_hint(route.max_level());
+            let filter: Box<dyn Filter<S> + Send + Sync> = match &output.filter_directives {
+                Some(directives) => Box::new(
+                    EnvFilter::try_new(directives.as_ref())
+                        .map_err(|err| InitError::InvalidFilterDirectives(err.to_string()))?,
+                ),
+                None => {
+                    let (filter, reload) = reload::Layer::new(build_filter(cfg, respect_env)?);
+                    handle.push(reload);
+                    Box::new(filter)
+                }
+            };
+            let layer = output_layer(
+                cfg,
+                output,
+                sink,
+                filter.or(panic_filter()).and(route_filter),
+                &base_fields,
+                &mut handle,
+            )?;
+            layers.push(layer);
+        }
+    }
+    Ok((layers, handle.build()))
+}
+
+fn output_layer<S, F>(
+    cfg: &Config,
+    output: &Output,
+    sink: Sink,
+    filter: F,
+    base_fields: &[(String, Value)],
+    handle: &mut HandleBuilder,
+) -> Result<Box<dyn Layer<S> + Send + Sync>, InitError>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    F: Filter<S> + Send + Sync + 'static,
+{
+    let (mut writer, event_metadata) = match sink.kind {
+        SinkKind::Writer {
+            writer,
+            event_metadata,
+        } => (writer, event_metadata),
+        #[cfg(unix)]
+        SinkKind::Journald(sink) => {
+            let mut journald = JournaldLayer::connect(sink.clone(), &cfg.name).map_err(|err| {
+                InitError::ConnectJournald(format!("{}: {err}", sink.path.display()))
+            })?;
+            journald.base_fields = base_fields.to_vec();
+            journald.span = cfg.span;
+            journald.flatten_span_fields = cfg.flatten_span_fields;
+            journald.metadata = metadata_fields(cfg);
+            journald.thread = thread_fields(cfg);
+            handle.push_dropped(journald.dropped.clone());
+            return Ok(journald.with_filter(filter).boxed());
+        }
+    };
+
+    let color = resolve_color(output.color, sink.tty);
+    if let Some(non_blocking) = cfg.non_blocking
+        && !event_metadata
+    {
+        let (non_blocking, worker) = NonBlockingWriter::spawn(writer, non_blocking)
+            .map_err(|err| InitError::SpawnWriter(err.to_string()))?;
+        handle.push_worker(worker);
+        writer = BoxMakeWriter::new(non_blocking);
+    }
+
+    let layer = tracing_subscriber::fmt::layer()
+        .with_ansi(color)
+        .with_writer(writer)
+        .event_format(build_formatter(
+            cfg,
+            output.output_format,
+            color,
+            base_fields,
+        ))
+        .with_filter(filter);
+    Ok(layer.boxed())
+}
 
-    let filter = build_filter(&cfg)?;
-    let use_color = resolve_color(&cfg);
-    let base_fields = build_base_fields(&cfg);
-    let formatter = match cfg.output_format {
+fn metadata_fields(cfg: &Config) -> MetadataFields {
+    MetadataFields {
+        target: cfg.include_target,
+        module_path: cfg.include_module_path,
+        file: cfg.include_file,
+        line: cfg.include_line,
+    }
+}
+
+fn thread_fields(cfg: &Config) -> ThreadFields {
+    ThreadFields {
+        name: cfg.include_thread_name,
+        id: cfg.include_thread_id,
+    }
+}
+
+fn build_formatter(
+    cfg: &Config,
+    output_format: OutputFormat,
+    color: bool,
+    base_fields: &[(String, Value)],
+) -> Formatter {
+    let metadata = metadata_fields(cfg);
+    let thread = thread_fields(cfg);
+
+    match output_format {
         OutputFormat::Json => Formatter::Json(JsonFormatter {
             name: cfg.name.clone(),
-            base_fields: base_fields.clone(),
+            base_fields: base_fields.to_vec(),
             span: cfg.span,
+            flatten_span_fields: cfg.flatten_span_fields,
+            metadata,
+            thread,
+            timestamp_format: cfg.timestamp_format,
+            timestamp_key: cfg.timestamp_key.clone(),
+        }),
+        OutputFormat::Logfmt => Formatter::Logfmt(LogfmtFormatter {
+            name: cfg.name.clone(),
+            base_fields: base_fields.to_vec(),
+            span: cfg.span,
+            metadata,
+            thread,
         }),
         OutputFormat::Plain => Formatter::Plain(PlainFormatter {
             name: cfg.name.clone(),
-            color: use_color,
-            base_fields,
+            color,
+            base_fields: base_fields.to_vec(),
             span: cfg.span,
+            span_path: cfg.span_path,
+            metadata,
+            thread,
+            time_style: cfg.time_style,
+            start: Instant::now(),
         }),
-    };
-
-    let result = match cfg.stream {
-        Stream::Stdout => {
-            let subscriber = tracing_subscriber::fmt()
-                .with_env_filter(filter)
-                .with_target(false)
-                .with_timer(SystemTime)
-                .with_writer(std::io::stdout)
-                .event_format(formatter)
-                .finish();
-            tracing::subscriber::set_global_default(subscriber)
-        }
-        Stream::Stderr => {
-            let subscriber = tracing_subscriber::fmt()
-                .with_env_filter(filter)
-                .with_target(false)
-                .with_timer(SystemTime)
-                .with_writer(std::io::stderr)
-                .event_format(formatter)
-                .finish();
-            tracing::subscriber::set_global_default(subscriber)
-        }
-    };
-
-    result.map_err(|err| InitError::SetGlobalDefault(err.to_string()))?;
-    let _ = INIT.set(());
-    Ok(())
+    }
 }
 
-pub fn is_initialized() -> bool {
-    INIT.get().is_some()
+fn panic_filter<S>() -> impl Filter<S> + Send + Sync + 'static {
+    filter_fn(crate::panic::is_panic_event).with_max_level_hint(LevelFilter::ERROR)
 }
 
-fn build_filter(cfg: &Config) -> Result<EnvFilter, InitError> {
-    if let Ok(filter) = EnvFilter::try_from_default_env() {
-        return Ok(filter);
+pub(crate) fn build_filter(cfg: &Config, respect_env: bool) -> Result<EnvFilter, InitError> {
+    if let Some(directives) = env_directives(respect_env) {
+        return Ok(EnvFilter::new(directives));
     }
 
     if let Some(directives) = &cfg.filter_directives {
@@ -81,20 +278,33 @@ fn build_filter(cfg: &Config) -> Result<EnvFilter, InitError> {
             .map_err(|err| InitError::InvalidFilterDirectives(err.to_string()));
     }
 
-    let level = match cfg.quiet {
-        0 => match cfg.verbose {
+    Ok(EnvFilter::new(level_filter(cfg.verbose, cfg.quiet)))
+}
+
+fn env_directives(respect_env: bool) -> Option<String> {
+    if !respect_env {
+        return None;
+    }
+    let directives = std::env::var(EnvFilter::DEFAULT_ENV).ok()?;
+    EnvFilter::try_new(&directives)
+        .is_ok()
+        .then_some(directives)
+}
+
+pub(crate) fn level_filter(verbose: u8, quiet: u8) -> &'static str {
+    match quiet {
+        0 => match verbose {
             0 => "info",
             1 => "debug",
             _ => "trace",
         },
         1 => "warn",
         _ => "error",
-    };
-    Ok(EnvFilter::new(level))
+    }
 }
 
-fn resolve_config(mut cfg: Config) -> Config {
-    let is_tty = is_tty(cfg.stream);
+pub(crate) fn resolve_config(mut cfg: Config) -> Config {
+    let is_tty = is_tty(&cfg.stream);
     if let Some(policy) = cfg.policy.take() {
         let override_cfg = if is_tty { policy.tty } else { policy.non_tty };
         apply_override(&mut cfg, override_cfg);
@@ -102,13 +312,6 @@ fn resolve_config(mut cfg: Config) -> Config {
     cfg
 }
 
-fn is_tty(stream: Stream) -> bool {
-    match stream {
-        Stream::Stdout => std::io::stdout().is_terminal(),
-        Stream::Stderr => std::io::stderr().is_terminal(),
-    }
-}
-
 fn apply_override(cfg: &mut Config, ov: ConfigOverride) {
     if let Some(v) = ov.output_format {
         cfg.output_format = v;
@@ -131,6 +334,21 @@ fn apply_override(cfg: &mut Config, ov: ConfigOverride) {
     if let Some(v) = ov.base_fields {
         cfg.base_fields = v;
     }
+    if let Some(v) = ov.outputs {
+        cfg.outputs = v;
+    }
+    if let Some(v) = ov.non_blocking {
+        cfg.non_blocking = Some(v);
+    }
+    if let Some(v) = ov.time_style {
+        cfg.time_style = v;
+    }
+    if let Some(v) = ov.timestamp_format {
+        cfg.timestamp_format = v;
+    }
+    if let Some(v) = ov.timestamp_key {
+        cfg.timestamp_key = v;
+    }
     if let Some(v) = ov.include_pid {
         cfg.include_pid = v;
     }
@@ -140,30 +358,101 @@ fn apply_override(cfg: &mut Config, ov: ConfigOverride) {
     if let Some(v) = ov.include_version {
         cfg.include_version = v;
     }
+    if let Some(v) = ov.include_target {
+        cfg.include_target = v;
+    }
+    if let Some(v) = ov.include_module_path {
+        cfg.include_module_path = v;
+    }
+    if let Some(v) = ov.include_file {
+        cfg.include_file = v;
+    }
+    if let Some(v) = ov.include_line {
+        cfg.include_line = v;
+    }
+    if let Some(v) = ov.include_thread_name {
+        cfg.include_thread_name = v;
+    }
+    if let Some(v) = ov.include_thread_id {
+        cfg.include_thread_id = v;
+    }
     if let Some(v) = ov.span {
         cfg.span = v;
     }
+    if let Some(v) = ov.flatten_span_fields {
+        cfg.flatten_span_fields = v;
+    }
+    if let Some(v) = ov.span_path {
+        cfg.span_path = v;
+    }
     if let Some(v) = ov.error_report {
         cfg.error_report = v;
     }
+    if let Some(v) = ov.report_hook {
+        cfg.report_hook = v;
+    }
+    #[cfg(feature = "log")]
+    if let Some(v) = ov.log_bridge {
+        cfg.log_bridge = v;
+    }
+    #[cfg(feature = "signal")]
+    if let Some(v) = ov.signal_verbosity {
+        cfg.signal_verbosity = v;
+    }
 }
 
-fn resolve_color(cfg: &Config) -> bool {
-    match cfg.color {
+fn resolve_color(color: Color, tty: bool) -> bool {
+    match color {
         Color::Always => true,
         Color::Never => false,
         Color::Auto => {
             if std::env::var_os("NO_COLOR").is_some() {
                 return false;
             }
-            match cfg.stream {
-                Stream::Stdout => std::io::stdout().is_terminal(),
-                Stream::Stderr => std::io::stderr().is_terminal(),
-            }
+            tty
         }
     }
 }
 
+fn report_hook_builder(cfg: &Config) -> HookBuilder {
+    let hook = &cfg.report_hook;
+    let theme = match hook.theme {
+        ReportTheme::Dark => Theme::dark(),
+        ReportTheme::Light => Theme::light(),
+        ReportTheme::Blank => Theme::new(),
+    };
+    let mut builder = HookBuilder::default()
+        .theme(theme)
+        .display_env_section(hook.env_section)
+        .display_location_section(hook.location_section);
+
+    if !hook.hidden_frames.is_empty() {
+        let prefixes = hook.hidden_frames.clone();
+        builder = builder.add_frame_filter(Box::new(move |frames| {
+            frames.retain(|frame| {
+                !frame.name.as_deref().is_some_and(|name| {
+                    prefixes
+                        .iter()
+                        .any(|prefix| name.starts_with(prefix.as_ref()))
+                })
+            });
+        }));
+    }
+
+    #[cfg(feature = "issue-url")]
+    if let Some(url) = &hook.issue_url {
+        builder = builder
+            .issue_url(url)
+            .add_issue_metadata("name", cfg.name.clone())
+            .add_issue_metadata("os", std::env::consts::OS);
+        if let Some(version) = &hook.version {
+            builder = builder.add_issue_metadata("version", version.clone());
+        }
+    }
+
+    builder
+}
+
 fn build_base_fields(cfg: &Config) -> Vec<(String, Value)> {
     let mut fields = BTreeMap::new();
 
@@ -198,7 +487,7 @@ fn build_base_fields(cfg: &Config) -> Vec<(String, Value)> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{ConfigOverride, Policy};
+    use crate::config::{ConfigOverride, Policy, ReportHook, Stream, TimeStyle, TimestampFormat};
 
     #[test]
     fn resolve_config_uses_tty_override() {
@@ -241,11 +530,24 @@ mod tests {
             .quiet(1)
             .filter_directives("a=debug")
             .base_fields(vec![("k".into(), Value::String("v".into()))])
+            .outputs(vec![Output::new(Stream::Stdout).json()])
+            .time_style(TimeStyle::Utc)
+            .timestamp_format(TimestampFormat::Rfc3339)
+            .timestamp_key("@timestamp")
             .include_pid(true)
             .include_exe(true)
             .include_version(true)
+            .include_target(true)
+            .include_module_path(true)
+            .include_file(true)
+            .include_line(true)
+            .include_thread_name(true)
+            .include_thread_id(true)
             .span(true)
-            .error_report(false);
+            .flatten_span_fields(true)
+            .span_path(true)
+            .error_report(false)
+            .report_hook(ReportHook::new().light().env_section(false));
         apply_override(&mut cfg, ov);
 
         assert!(matches!(cfg.output_format, OutputFormat::Json));
@@ -255,10 +557,24 @@ mod tests {
         assert_eq!(cfg.quiet, 1);
         assert_eq!(cfg.filter_directives.as_deref(), Some("a=debug"));
         assert_eq!(cfg.base_fields.len(), 1);
+        assert_eq!(cfg.outputs.len(), 1);
+        assert_eq!(cfg.time_style, TimeStyle::Utc);
+        assert_eq!(cfg.timestamp_format, TimestampFormat::Rfc3339);
+        assert_eq!(cfg.timestamp_key, "@timestamp");
         assert!(cfg.include_pid);
         assert!(cfg.include_exe);
         assert!(cfg.include_version);
+        assert!(cfg.include_target);
+        assert!(cfg.include_module_path);
+        assert!(cfg.include_file);
+        assert!(cfg.include_line);
+        assert!(cfg.include_thread_name);
+        assert!(cfg.include_thread_id);
         assert!(cfg.span);
+        assert!(cfg.flatten_span_fields);
+        assert!(cfg.span_path);
         assert!(!cfg.error_report);
+        assert_eq!(cfg.report_hook.theme, ReportTheme::Light);
+        assert!(!cfg.report_hook.env_section);
     }
 }
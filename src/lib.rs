@@ -1,8 +1,32 @@
 mod config;
 mod error;
 mod formatter;
+mod handle;
 mod init;
-pub use config::{Color, Config, ConfigOverride, OutputFormat, Policy, Stream};
+mod panic;
+mod report;
+#[cfg(all(feature = "signal", unix))]
+mod signal;
+mod writer;
+
+pub mod testing;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::report::eyre_backtrace;
+    pub use color_eyre::eyre::Report;
+}
+
+pub use config::{
+    Color, Config, ConfigOverride, Output, OutputFormat, Policy, ReportHook, ReportTheme, Stream,
+    TimeStyle, TimestampFormat,
+};
 pub use error::InitError;
-pub use init::{init, is_initialized, try_init};
+pub use handle::{FlushGuard, Handle};
+pub use init::{init, is_initialized, layer, set_default, try_init};
+pub use report::{report_error, report_eyre};
+pub use tracing::subscriber::DefaultGuard;
 pub use tracing::{debug, error, info, trace, warn};
+pub use writer::{CustomWriter, FileSink, NonBlocking, Overflow, Rotation};
+#[cfg(unix)]
+pub use writer::{Facility, JournaldSink, SyslogFormat, SyslogSink};
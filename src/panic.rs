@@ -0,0 +1,104 @@
+use std::{
+    backtrace::{Backtrace, BacktraceStatus},
+    panic::PanicHookInfo,
+};
+use tracing::Metadata;
+
+const TARGET: &str = "panic";
+
+type PanicHook = Box<dyn Fn(&PanicHookInfo<'_>) + Send + Sync + 'static>;
+
+pub(crate) fn install(chain: Option<PanicHook>) {
+    std::panic::set_hook(Box::new(move |info| {
+        emit(info);
+        if let Some(chain) = &chain {
+            chain(info);
+        }
+    }));
+}
+
+fn emit(info: &PanicHookInfo<'_>) {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>");
+    let location = info
+        .location()
+        .map(|location| location.to_string())
+        .unwrap_or_default();
+    let thread = std::thread::current();
+    let thread = thread.name().unwrap_or("<unnamed>");
+    let backtrace = Backtrace::capture();
+    let backtrace = (backtrace.status() == BacktraceStatus::Captured)
+        .then(|| frames(&backtrace.to_string()).join("\n"));
+
+    record(message, &location, thread, backtrace);
+}
+
+fn record(message: &str, location: &str, thread: &str, backtrace: Option<String>) {
+    tracing::error!(
+        target: TARGET,
+        location,
+        thread,
+        backtrace,
+        "{message}"
+    );
+}
+
+pub(crate) fn is_panic_event(metadata: &Metadata<'_>) -> bool {
+    metadata.target() == TARGET && metadata.module_path() == Some(module_path!())
+}
+
+fn frames(backtrace: &str) -> Vec<String> {
+    let mut frames: Vec<String> = Vec::new();
+    for line in backtrace.lines().map(str::trim) {
+        match frames.last_mut() {
+            Some(frame) if line.starts_with("at ") => {
+                frame.push(' ');
+                frame.push_str(line);
+            }
+            _ if line.is_empty() => {}
+            _ => frames.push(line.to_string()),
+        }
+    }
+    frames
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_source_locations_with_their_frame() {
+        let backtrace = "   0: app::main\n             at ./src/main.rs:4:5\n   1: std::rt::lang_start\n   2: main\n";
+        assert_eq!(
+            frames(backtrace),
+            [
+                "0: app::main at ./src/main.rs:4:5",
+                "1: std::rt::lang_start",
+                "2: main",
+            ]
+        );
+    }
+
+    #[test]
+    fn json_output_renders_backtrace_as_frame_array() {
+        let cap = crate::testing::capture(crate::Config::new("traxer-panic").json())
+            .expect("capture should start");
+        record(
+            "boom",
+            "src/main.rs:4:5",
+            "main",
+            Some("0: app::main at ./src/main.rs:4:5\n1: main".to_string()),
+        );
+
+        let line: serde_json::Value =
+            serde_json::from_str(&cap.lines()[0]).expect("invalid json line");
+        assert_eq!(
+            line["backtrace"],
+            serde_json::json!(["0: app::main at ./src/main.rs:4:5", "1: main"])
+        );
+    }
+}
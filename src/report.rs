@@ -0,0 +1,41 @@
+use color_eyre::eyre::Report;
+use std::error::Error;
+
+pub fn report_error(err: &(dyn Error + 'static)) {
+    emit(err, None);
+}
+
+pub fn report_eyre(report: &Report) {
+    emit(report.as_ref(), eyre_backtrace(report));
+}
+
+#[doc(hidden)]
+pub fn eyre_backtrace(report: &Report) -> Option<String> {
+    report
+        .handler()
+        .downcast_ref::<color_eyre::Handler>()
+        .and_then(color_eyre::Handler::backtrace)
+        .map(|backtrace| format!("{backtrace:?}"))
+}
+
+fn emit(err: &(dyn Error + 'static), backtrace: Option<String>) {
+    tracing::error!(error.message = err, error.backtrace = backtrace, "{err}");
+}
+
+#[macro_export]
+macro_rules! report_error {
+    ($err:expr $(,)?) => {{
+        let err: &(dyn ::std::error::Error + 'static) = &$err;
+        $crate::error!(error.message = err, "{}", err)
+    }};
+}
+
+#[macro_export]
+macro_rules! report_eyre {
+    ($report:expr $(,)?) => {{
+        let report: &$crate::__private::Report = &$report;
+        let err: &(dyn ::std::error::Error + 'static) = report.as_ref();
+        let backtrace = $crate::__private::eyre_backtrace(report);
+        $crate::error!(error.message = err, error.backtrace = backtrace, "{}", err)
+    }};
+}
@@ -0,0 +1,31 @@
+use crate::error::InitError;
+use crate::handle::Handle;
+use signal_hook::{
+    consts::{SIGUSR1, SIGUSR2},
+    iterator::Signals,
+};
+
+pub(crate) fn spawn(handle: Handle) -> Result<(), InitError> {
+    let mut signals = Signals::new([SIGUSR1, SIGUSR2])
+        .map_err(|err| InitError::InstallSignalHandler(err.to_string()))?;
+
+    std::thread::Builder::new()
+        .name("traxer-signal".to_string())
+        .spawn(move || {
+            for signal in signals.forever() {
+                let (name, delta) = match signal {
+                    SIGUSR1 => ("SIGUSR1", 1),
+                    _ => ("SIGUSR2", -1),
+                };
+                if let Err(err) = handle.step_verbosity(delta, |level| log_change(name, level)) {
+                    tracing::error!(signal = name, error = %err, "failed to change log verbosity");
+                }
+            }
+        })
+        .map_err(|err| InitError::InstallSignalHandler(err.to_string()))?;
+    Ok(())
+}
+
+fn log_change(signal: &str, level: &str) {
+    tracing::info!(signal, level, "log verbosity changed");
+}
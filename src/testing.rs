@@ -0,0 +1,159 @@
+use crate::config::{Config, Stream};
+use crate::error::InitError;
+use crate::formatter::{JsonFieldVisitor, normalized_metadata};
+use crate::init::{build_filter, build_layers, resolve_config};
+use crate::writer::CustomWriter;
+use serde_json::{Map, Value};
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+use tracing::{Event, Subscriber, subscriber::DefaultGuard};
+use tracing_subscriber::{
+    Layer,
+    layer::{Context, SubscriberExt},
+};
+
+pub use tracing::Level;
+
+pub fn capture(cfg: Config) -> Result<Capture, InitError> {
+    let buffer = Arc::new(Buffer::default());
+    let mut cfg = cfg;
+    cfg.stream = Stream::Custom(CustomWriter::new(buffer.clone()));
+    cfg.outputs.clear();
+    cfg.non_blocking = None;
+    let cfg = resolve_config(cfg);
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let (mut layers, _) = build_layers(&cfg, false)?;
+    layers.push(
+        EventLayer {
+            events: events.clone(),
+        }
+        .with_filter(build_filter(&cfg, false)?)
+        .boxed(),
+    );
+    let subscriber = tracing_subscriber::registry().with(layers);
+
+    Ok(Capture {
+        buffer,
+        events,
+        _guard: tracing::subscriber::set_default(subscriber),
+    })
+}
+
+pub struct Capture {
+    buffer: Arc<Buffer>,
+    events: Arc<Mutex<Vec<CapturedEvent>>>,
+    _guard: DefaultGuard,
+}
+
+impl Capture {
+    pub fn output(&self) -> String {
+        let bytes = lock(&self.buffer.bytes);
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        self.output().lines().map(str::to_string).collect()
+    }
+
+    pub fn events(&self) -> Vec<CapturedEvent> {
+        lock(&self.events).clone()
+    }
+
+    pub fn contains(&self, needle: &str) -> bool {
+        self.output().contains(needle)
+    }
+
+    pub fn has_event(&self, level: Level, fields: &[(&str, Value)]) -> bool {
+        lock(&self.events)
+            .iter()
+            .any(|event| event.matches(level, fields))
+    }
+
+    #[track_caller]
+    pub fn assert_event(&self, level: Level, fields: &[(&str, Value)]) {
+        assert!(
+            self.has_event(level, fields),
+            "no {level} event with fields {fields:?} was logged.\noutput:\n{}",
+            self.output()
+        );
+    }
+
+    #[track_caller]
+    pub fn assert_no_event(&self, level: Level, fields: &[(&str, Value)]) {
+        assert!(
+            !self.has_event(level, fields),
+            "unexpected {level} event with fields {fields:?} was logged.\noutput:\n{}",
+            self.output()
+        );
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CapturedEvent {
+    pub level: Level,
+    pub target: String,
+    pub fields: Map<String, Value>,
+}
+
+impl CapturedEvent {
+    pub fn message(&self) -> Option<&str> {
+        self.fields.get("message").and_then(Value::as_str)
+    }
+
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        self.fields.get(name)
+    }
+
+    pub fn matches(&self, level: Level, fields: &[(&str, Value)]) -> bool {
+        self.level == level
+            && fields
+                .iter()
+                .all(|(name, value)| self.fields.get(*name) == Some(value))
+    }
+}
+
+#[derive(Default)]
+struct Buffer {
+    bytes: Mutex<Vec<u8>>,
+}
+
+impl io::Write for &Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        lock(&self.bytes).extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct EventLayer {
+    events: Arc<Mutex<Vec<CapturedEvent>>>,
+}
+
+impl<S: Subscriber> Layer<S> for EventLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let normalized = normalized_metadata(event);
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+        let mut fields = Map::new();
+        event.record(&mut JsonFieldVisitor {
+            map: &mut fields,
+            log_record: normalized.is_some(),
+        });
+        lock(&self.events).push(CapturedEvent {
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            fields,
+        });
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
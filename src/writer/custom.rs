@@ -0,0 +1,45 @@
+use std::{io::Write, sync::Arc};
+use tracing::Metadata;
+use tracing_subscriber::fmt::{MakeWriter, writer::BoxMakeWriter};
+
+#[derive(Clone)]
+pub struct CustomWriter {
+    inner: Arc<BoxMakeWriter>,
+    pub tty: bool,
+}
+
+impl CustomWriter {
+    pub fn new<M>(make_writer: M) -> Self
+    where
+        M: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+    {
+        Self {
+            inner: Arc::new(BoxMakeWriter::new(make_writer)),
+            tty: false,
+        }
+    }
+    pub fn tty(mut self, is_tty: bool) -> Self {
+        self.tty = is_tty;
+        self
+    }
+}
+
+impl<'a> MakeWriter<'a> for CustomWriter {
+    type Writer = Box<dyn Write + 'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.inner.make_writer()
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        self.inner.make_writer_for(meta)
+    }
+}
+
+impl std::fmt::Debug for CustomWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CustomWriter")
+            .field("tty", &self.tty)
+            .finish_non_exhaustive()
+    }
+}
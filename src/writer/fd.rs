@@ -0,0 +1,53 @@
+use std::{
+    fs::File,
+    io,
+    os::fd::{FromRawFd, RawFd},
+};
+
+pub(crate) fn dup(fd: RawFd) -> io::Result<File> {
+    // SAFETY: `fcntl` with `F_DUPFD_CLOEXEC` takes no pointers and only
+    // reads `fd`; an invalid or closed descriptor is reported as -1/EBADF.
+    let duplicated = unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) };
+    if duplicated < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: `duplicated` is a fresh descriptor returned by `fcntl` above.
+    // Nothing else refers to it, so the `File` becomes its sole owner.
+    Ok(unsafe { File::from_raw_fd(duplicated) })
+}
+
+pub(crate) fn is_terminal(fd: RawFd) -> bool {
+    // SAFETY: `isatty` takes no pointers and only queries `fd`; an invalid
+    // descriptor yields 0 with EBADF.
+    unsafe { libc::isatty(fd) == 1 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{Read, Write},
+        os::fd::AsRawFd,
+    };
+
+    #[test]
+    fn duplicates_fd_independently_of_original() {
+        let (mut reader, writer) = io::pipe().unwrap();
+        let mut file = dup(writer.as_raw_fd()).unwrap();
+        drop(writer);
+
+        file.write_all(b"hello\n").unwrap();
+        drop(file);
+
+        let mut out = String::new();
+        reader.read_to_string(&mut out).unwrap();
+        assert_eq!(out, "hello\n");
+        assert!(!is_terminal(reader.as_raw_fd()));
+    }
+
+    #[test]
+    fn rejects_closed_fd() {
+        assert!(dup(-1).is_err());
+        assert!(!is_terminal(-1));
+    }
+}
@@ -0,0 +1,250 @@
+use crate::formatter::time::local_offset_secs;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing_subscriber::fmt::MakeWriter;
+
+const SECS_PER_DAY: i64 = 86_400;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rotation {
+    Never,
+    /// Rolls over at local midnight, or at UTC midnight where the local
+    /// offset is unavailable (see `TimeStyle::Local`).
+    Daily,
+}
+
+#[derive(Clone, Debug)]
+pub struct FileSink {
+    pub path: PathBuf,
+    pub max_size: Option<u64>,
+    pub rotation: Rotation,
+    pub keep: usize,
+}
+
+impl FileSink {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            max_size: None,
+            rotation: Rotation::Never,
+            keep: 5,
+        }
+    }
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+    pub fn rotation(mut self, rotation: Rotation) -> Self {
+        self.rotation = rotation;
+        self
+    }
+    pub fn daily(mut self) -> Self {
+        self.rotation = Rotation::Daily;
+        self
+    }
+    pub fn keep(mut self, keep: usize) -> Self {
+        self.keep = keep;
+        self
+    }
+}
+
+pub(crate) struct RollingFile {
+    sink: FileSink,
+    state: Mutex<State>,
+}
+
+struct State {
+    file: Option<File>,
+    size: u64,
+    day: u64,
+}
+
+impl RollingFile {
+    pub(crate) fn open(sink: FileSink) -> io::Result<Self> {
+        if let Some(parent) = sink.path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = open_append(&sink.path)?;
+        let metadata = file.metadata()?;
+        let day = metadata
+            .modified()
+            .map(day_of)
+            .unwrap_or_else(|_| day_of(SystemTime::now()));
+
+        Ok(Self {
+            sink,
+            state: Mutex::new(State {
+                file: Some(file),
+                size: metadata.len(),
+                day,
+            }),
+        })
+    }
+
+    fn should_rotate(&self, state: &State, incoming: usize, today: u64) -> bool {
+        if state.size == 0 {
+            return false;
+        }
+
+        let too_large = self
+            .sink
+            .max_size
+            .is_some_and(|max| state.size + incoming as u64 > max);
+        let new_day = self.sink.rotation == Rotation::Daily && today != state.day;
+        too_large || new_day
+    }
+
+    fn rotate(&self, state: &mut State) -> io::Result<()> {
+        if let Some(mut file) = state.file.take() {
+            file.flush()?;
+        }
+
+        let path = &self.sink.path;
+        if self.sink.keep == 0 {
+            remove_if_exists(path)?;
+        } else {
+            remove_if_exists(&backup_path(path, self.sink.keep))?;
+            for index in (1..self.sink.keep).rev() {
+                let from = backup_path(path, index);
+                if from.exists() {
+                    fs::rename(&from, backup_path(path, index + 1))?;
+                }
+            }
+            fs::rename(path, backup_path(path, 1))?;
+        }
+
+        state.file = Some(open_append(path)?);
+        state.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for &RollingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let today = day_of(SystemTime::now());
+        if self.should_rotate(&state, buf.len(), today) {
+            self.rotate(&mut state)?;
+        }
+        state.day = today;
+
+        let file = match state.file.as_mut() {
+            Some(file) => file,
+            None => state.file.insert(open_append(&self.sink.path)?),
+        };
+        file.write_all(buf)?;
+        state.size += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match state.file.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for RollingFile {
+    type Writer = &'a RollingFile;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+fn backup_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
+}
+
+fn day_of(time: SystemTime) -> u64 {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0);
+    let local = secs + local_offset_secs(time).unwrap_or(0);
+    local.div_euclid(SECS_PER_DAY).max(0) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("traxer-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn rotates_by_size_and_keeps_limited_backups() {
+        let dir = temp_dir("size");
+        let path = dir.join("app.log");
+        let file = RollingFile::open(FileSink::new(&path).max_size(10).keep(2)).unwrap();
+
+        for line in ["aaaaaaaa\n", "bbbbbbbb\n", "cccccccc\n", "dddddddd\n"] {
+            (&file).write_all(line.as_bytes()).unwrap();
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "dddddddd\n");
+        assert_eq!(
+            fs::read_to_string(backup_path(&path, 1)).unwrap(),
+            "cccccccc\n"
+        );
+        assert_eq!(
+            fs::read_to_string(backup_path(&path, 2)).unwrap(),
+            "bbbbbbbb\n"
+        );
+        assert!(!backup_path(&path, 3).exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rotates_when_day_changes() {
+        let dir = temp_dir("daily");
+        let path = dir.join("app.log");
+        let file = RollingFile::open(FileSink::new(&path).daily()).unwrap();
+
+        (&file).write_all(b"yesterday\n").unwrap();
+        file.state.lock().unwrap().day -= 1;
+        (&file).write_all(b"today\n").unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "today\n");
+        assert_eq!(
+            fs::read_to_string(backup_path(&path, 1)).unwrap(),
+            "yesterday\n"
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::formatter::{
+    JsonFieldVisitor, MetadataFields, SpanFields, ThreadFields, normalized_metadata,
+};
+use serde_json::{Map, Value};
+use std::{
+    io,
+    os::unix::net::UnixDatagram,
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::{Layer, layer::Context, registry::LookupSpan};
+
+#[derive(Clone, Debug)]
+pub struct JournaldSink {
+    pub path: PathBuf,
+}
+
+impl JournaldSink {
+    pub fn new() -> Self {
+        Self {
+            path: PathBuf::from("/run/systemd/journal/socket"),
+        }
+    }
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = path.into();
+        self
+    }
+}
+
+impl Default for JournaldSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) struct JournaldLayer {
+    socket: UnixDatagram,
+    sink: JournaldSink,
+    pub(crate) identifier: String,
+    pub(crate) base_fields: Vec<(String, Value)>,
+    pub(crate) span: bool,
+    pub(crate) flatten_span_fields: bool,
+    pub(crate) metadata: MetadataFields,
+    pub(crate) thread: ThreadFields,
+    pub(crate) dropped: Arc<AtomicU64>,
+}
+
+impl JournaldLayer {
+    pub(crate) fn connect(sink: JournaldSink, identifier: &str) -> io::Result<Self> {
+        let socket = UnixDatagram::unbound()?;
+        socket.connect(&sink.path)?;
+        Ok(Self {
+            socket,
+            sink,
+            identifier: identifier.to_string(),
+            base_fields: Vec::new(),
+            span: false,
+            flatten_span_fields: false,
+            metadata: MetadataFields::default(),
+            thread: ThreadFields::default(),
+            dropped: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    fn send(&self, datagram: &[u8]) {
+        let sent = self.socket.send(datagram).is_ok()
+            || (self.socket.connect(&self.sink.path).is_ok() && self.socket.send(datagram).is_ok());
+        if !sent {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+impl<S> Layer<S> for JournaldLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let normalized = normalized_metadata(event);
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+        let mut fields = Map::new();
+        event.record(&mut JsonFieldVisitor {
+            map: &mut fields,
+            log_record: normalized.is_some(),
+        });
+
+        let mut datagram = Vec::new();
+        let priority = priority(metadata.level()).to_string();
+        put(&mut datagram, "PRIORITY", &priority);
+        put(&mut datagram, "SYSLOG_IDENTIFIER", &self.identifier);
+        let message = match fields.remove("message") {
+            Some(Value::String(message)) => message,
+            Some(other) => other.to_string(),
+            None => String::new(),
+        };
+        put(&mut datagram, "MESSAGE", &message);
+
+        for (key, value) in &self.base_fields {
+            fields.insert(key.clone(), value.clone());
+        }
+        if self.flatten_span_fields
+            && let Some(scope) = ctx.event_scope(event)
+        {
+            let mut flattened = Map::new();
+            for span in scope.from_root() {
+                if let Some(span_fields) = span.extensions().get::<SpanFields>() {
+                    flattened.extend(span_fields.0.clone());
+                }
+            }
+            flattened.remove("message");
+            for (key, value) in flattened {
+                fields.entry(key).or_insert(value);
+            }
+        }
+        for (key, value) in &fields {
+            put(&mut datagram, &field_name(key), &value_string(value));
+        }
+
+        for (key, value) in self
+            .metadata
+            .collect(metadata)
+            .into_iter()
+            .chain(self.thread.collect())
+        {
+            let name = match key {
+                "module_path" => "CODE_MODULE".to_string(),
+                "file" => "CODE_FILE".to_string(),
+                "line" => "CODE_LINE".to_string(),
+                other => field_name(other),
+            };
+            put(&mut datagram, &name, &value_string(&value));
+        }
+
+        if self.span
+            && let Some(span) = ctx.lookup_current()
+        {
+            put(&mut datagram, "SPAN", span.name());
+        }
+
+        self.send(&datagram);
+    }
+}
+
+fn priority(level: &Level) -> u8 {
+    match *level {
+        Level::ERROR => 3,
+        Level::WARN => 4,
+        Level::INFO => 6,
+        Level::DEBUG | Level::TRACE => 7,
+    }
+}
+
+const RESERVED_FIELDS: [&str; 3] = ["PRIORITY", "SYSLOG_IDENTIFIER", "MESSAGE"];
+
+fn field_name(key: &str) -> String {
+    let mut name: String = key
+        .chars()
+        .map(|c| match c.to_ascii_uppercase() {
+            c @ ('A'..='Z' | '0'..='9') => c,
+            _ => '_',
+        })
+        .collect();
+    let trimmed = name.trim_start_matches('_').len();
+    name.drain(..name.len() - trimmed);
+    if name.is_empty()
+        || name.starts_with(|c: char| c.is_ascii_digit())
+        || RESERVED_FIELDS.contains(&name.as_str())
+    {
+        name.insert_str(0, "F_");
+    }
+    name
+}
+
+fn value_string(value: &Value) -> String {
+    match value {
+        Value::String(value) => value.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn put(datagram: &mut Vec<u8>, name: &str, value: &str) {
+    datagram.extend_from_slice(name.as_bytes());
+    if value.contains('\n') {
+        datagram.push(b'\n');
+        datagram.extend_from_slice(&(value.len() as u64).to_le_bytes());
+    } else {
+        datagram.push(b'=');
+    }
+    datagram.extend_from_slice(value.as_bytes());
+    datagram.push(b'\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitizes_field_names() {
+        assert_eq!(field_name("user_id"), "USER_ID");
+        assert_eq!(field_name("http.status"), "HTTP_STATUS");
+        assert_eq!(field_name("_trusted"), "TRUSTED");
+        assert_eq!(field_name("1st"), "F_1ST");
+        assert_eq!(field_name("priority"), "F_PRIORITY");
+        assert_eq!(field_name("_message"), "F_MESSAGE");
+    }
+
+    #[test]
+    fn encodes_multiline_values_with_length_prefix() {
+        let mut datagram = Vec::new();
+        put(&mut datagram, "MESSAGE", "one line");
+        put(&mut datagram, "STACK", "a\nb");
+
+        let mut expected = b"MESSAGE=one line\nSTACK\n".to_vec();
+        expected.extend_from_slice(&3u64.to_le_bytes());
+        expected.extend_from_slice(b"a\nb\n");
+        assert_eq!(datagram, expected);
+    }
+}
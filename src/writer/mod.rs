@@ -0,0 +1,140 @@
+mod custom;
+#[cfg(unix)]
+mod fd;
+mod file;
+#[cfg(unix)]
+mod journald;
+mod non_blocking;
+#[cfg(unix)]
+mod syslog;
+
+use crate::config::Stream;
+use crate::error::InitError;
+use std::io::IsTerminal;
+use tracing::Level;
+use tracing_subscriber::{
+    filter::LevelFilter,
+    fmt::{MakeWriter, writer::BoxMakeWriter},
+};
+
+pub use custom::CustomWriter;
+pub(crate) use file::RollingFile;
+pub use file::{FileSink, Rotation};
+#[cfg(unix)]
+pub(crate) use journald::JournaldLayer;
+#[cfg(unix)]
+pub use journald::JournaldSink;
+pub use non_blocking::{NonBlocking, Overflow};
+pub(crate) use non_blocking::{NonBlockingWriter, Worker};
+#[cfg(unix)]
+pub(crate) use syslog::SyslogWriter;
+#[cfg(unix)]
+pub use syslog::{Facility, SyslogFormat, SyslogSink};
+
+#[derive(Clone, Copy)]
+pub(crate) enum Route {
+    All,
+    BelowWarn,
+    WarnAndAbove,
+}
+
+impl Route {
+    pub(crate) fn allows(self, level: Level) -> bool {
+        match self {
+            Self::All => true,
+            Self::BelowWarn => level > Level::WARN,
+            Self::WarnAndAbove => level <= Level::WARN,
+        }
+    }
+
+    pub(crate) fn max_level(self) -> LevelFilter {
+        match self {
+            Self::All | Self::BelowWarn => LevelFilter::TRACE,
+            Self::WarnAndAbove => LevelFilter::WARN,
+        }
+    }
+}
+
+pub(crate) struct Sink {
+    pub(crate) route: Route,
+    pub(crate) tty: bool,
+    pub(crate) kind: SinkKind,
+}
+
+pub(crate) enum SinkKind {
+    Writer {
+        writer: BoxMakeWriter,
+        event_metadata: bool,
+    },
+    #[cfg(unix)]
+    Journald(JournaldSink),
+}
+
+pub(crate) fn sinks(name: &str, stream: &Stream) -> Result<Vec<Sink>, InitError> {
+    #[cfg(not(unix))]
+    let _ = name;
+    let kind = match stream {
+        Stream::Stdout => writer(std::io::stdout),
+        Stream::Stderr => writer(std::io::stderr),
+        Stream::Split => {
+            return Ok(vec![
+                Sink {
+                    route: Route::BelowWarn,
+                    tty: is_tty(&Stream::Stdout),
+                    kind: writer(std::io::stdout),
+                },
+                Sink {
+                    route: Route::WarnAndAbove,
+                    tty: is_tty(&Stream::Stderr),
+                    kind: writer(std::io::stderr),
+                },
+            ]);
+        }
+        Stream::Custom(custom) => writer(custom.clone()),
+        #[cfg(unix)]
+        Stream::Fd(fd) => fd::dup(*fd)
+            .map(|file| writer(std::sync::Arc::new(file)))
+            .map_err(|err| InitError::OpenLogFd(format!("{fd}: {err}")))?,
+        #[cfg(unix)]
+        Stream::Syslog(sink) => SyslogWriter::connect(sink.clone(), name)
+            .map(|syslog| SinkKind::Writer {
+                writer: BoxMakeWriter::new(syslog),
+                event_metadata: true,
+            })
+            .map_err(|err| InitError::ConnectSyslog(format!("{}: {err}", sink.path.display())))?,
+        #[cfg(unix)]
+        Stream::Journald(sink) => SinkKind::Journald(sink.clone()),
+        Stream::File(sink) => RollingFile::open(sink.clone())
+            .map(writer)
+            .map_err(|err| InitError::OpenLogFile(format!("{}: {err}", sink.path.display())))?,
+    };
+    Ok(vec![Sink {
+        route: Route::All,
+        tty: is_tty(stream),
+        kind,
+    }])
+}
+
+fn writer<W>(make_writer: W) -> SinkKind
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    SinkKind::Writer {
+        writer: BoxMakeWriter::new(make_writer),
+        event_metadata: false,
+    }
+}
+
+pub(crate) fn is_tty(stream: &Stream) -> bool {
+    match stream {
+        Stream::Stdout => std::io::stdout().is_terminal(),
+        Stream::Stderr => std::io::stderr().is_terminal(),
+        Stream::Split => std::io::stdout().is_terminal() && std::io::stderr().is_terminal(),
+        Stream::File(_) => false,
+        Stream::Custom(custom) => custom.tty,
+        #[cfg(unix)]
+        Stream::Fd(fd) => fd::is_terminal(*fd),
+        #[cfg(unix)]
+        Stream::Syslog(_) | Stream::Journald(_) => false,
+    }
+}
@@ -0,0 +1,246 @@
+use std::{
+    io::{self, Write},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, SyncSender, TrySendError},
+    },
+};
+use tracing_subscriber::fmt::{MakeWriter, writer::BoxMakeWriter};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Overflow {
+    Block,
+    Drop,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct NonBlocking {
+    pub capacity: usize,
+    pub overflow: Overflow,
+}
+
+impl NonBlocking {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            overflow: Overflow::Block,
+        }
+    }
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+    pub fn block(mut self) -> Self {
+        self.overflow = Overflow::Block;
+        self
+    }
+    pub fn drop_when_full(mut self) -> Self {
+        self.overflow = Overflow::Drop;
+        self
+    }
+}
+
+impl Default for NonBlocking {
+    fn default() -> Self {
+        Self::new(8192)
+    }
+}
+
+enum Message {
+    Line(Vec<u8>),
+    Flush(mpsc::Sender<()>),
+}
+
+pub(crate) struct NonBlockingWriter {
+    sender: SyncSender<Message>,
+    overflow: Overflow,
+    dropped: Arc<AtomicU64>,
+}
+
+#[derive(Clone)]
+pub(crate) struct Worker {
+    sender: SyncSender<Message>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl NonBlockingWriter {
+    pub(crate) fn spawn(inner: BoxMakeWriter, settings: NonBlocking) -> io::Result<(Self, Worker)> {
+        if settings.capacity == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "non-blocking capacity must be greater than zero",
+            ));
+        }
+        let (sender, receiver) = mpsc::sync_channel(settings.capacity);
+        let dropped = Arc::new(AtomicU64::new(0));
+
+        std::thread::Builder::new()
+            .name("traxer-writer".to_string())
+            .spawn(move || {
+                for message in receiver {
+                    match message {
+                        Message::Line(line) => {
+                            let _ = inner.make_writer().write_all(&line);
+                        }
+                        Message::Flush(done) => {
+                            let _ = inner.make_writer().flush();
+                            let _ = done.send(());
+                        }
+                    }
+                }
+            })?;
+
+        let worker = Worker {
+            sender: sender.clone(),
+            dropped: dropped.clone(),
+        };
+        let writer = Self {
+            sender,
+            overflow: settings.overflow,
+            dropped,
+        };
+        Ok((writer, worker))
+    }
+}
+
+impl Write for &NonBlockingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let message = Message::Line(buf.to_vec());
+        match self.overflow {
+            Overflow::Block => self
+                .sender
+                .send(message)
+                .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?,
+            Overflow::Drop => match self.sender.try_send(message) {
+                Ok(()) => {}
+                Err(TrySendError::Full(_)) => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(TrySendError::Disconnected(_)) => {
+                    return Err(io::ErrorKind::BrokenPipe.into());
+                }
+            },
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        flush(&self.sender)
+    }
+}
+
+impl<'a> MakeWriter<'a> for NonBlockingWriter {
+    type Writer = &'a NonBlockingWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self
+    }
+}
+
+impl Worker {
+    pub(crate) fn flush(&self) {
+        let _ = flush(&self.sender);
+    }
+
+    pub(crate) fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+fn flush(sender: &SyncSender<Message>) -> io::Result<()> {
+    let (done, wait) = mpsc::channel();
+    sender
+        .send(Message::Flush(done))
+        .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+    wait.recv()
+        .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Clone, Default)]
+    struct Gate {
+        lines: Arc<Mutex<Vec<u8>>>,
+    }
+
+    impl Write for Gate {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.lines.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn drop_policy_counts_lines_that_do_not_fit() {
+        let gate = Gate::default();
+        let held = gate.lines.lock().unwrap();
+        let inner = gate.clone();
+        let (writer, worker) = NonBlockingWriter::spawn(
+            BoxMakeWriter::new(move || inner.clone()),
+            NonBlocking::new(1).drop_when_full(),
+        )
+        .unwrap();
+
+        (&writer).write_all(b"first\n").unwrap();
+        while writer.sender.try_send(Message::Line(Vec::new())).is_err() {
+            std::thread::yield_now();
+        }
+        (&writer).write_all(b"dropped\n").unwrap();
+        assert_eq!(worker.dropped(), 1);
+
+        drop(held);
+        worker.flush();
+        assert_eq!(gate.lines.lock().unwrap().as_slice(), b"first\n");
+    }
+
+    #[test]
+    fn block_policy_delivers_every_line() {
+        let gate = Gate::default();
+        let inner = gate.clone();
+        let (writer, worker) = NonBlockingWriter::spawn(
+            BoxMakeWriter::new(move || inner.clone()),
+            NonBlocking::new(2),
+        )
+        .unwrap();
+
+        for _ in 0..100 {
+            (&writer).write_all(b"line\n").unwrap();
+        }
+        worker.flush();
+
+        assert_eq!(gate.lines.lock().unwrap().len(), 500);
+        assert_eq!(worker.dropped(), 0);
+    }
+
+    #[test]
+    fn writer_flush_waits_for_queued_lines() {
+        let gate = Gate::default();
+        let inner = gate.clone();
+        let (writer, _worker) = NonBlockingWriter::spawn(
+            BoxMakeWriter::new(move || inner.clone()),
+            NonBlocking::new(4),
+        )
+        .unwrap();
+
+        (&writer).write_all(b"queued\n").unwrap();
+        (&writer).flush().unwrap();
+
+        assert_eq!(gate.lines.lock().unwrap().as_slice(), b"queued\n");
+    }
+
+    #[test]
+    fn zero_capacity_is_rejected() {
+        let err = NonBlockingWriter::spawn(BoxMakeWriter::new(std::io::sink), NonBlocking::new(0))
+            .err()
+            .expect("zero capacity should fail");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,257 @@
+use crate::formatter::time::{bsd_timestamp, local_offset_secs, rfc3339_utc};
+use std::{
+    io::{self, Write},
+    os::unix::net::UnixDatagram,
+    path::PathBuf,
+    time::SystemTime,
+};
+use tracing::{Level, Metadata};
+use tracing_subscriber::fmt::MakeWriter;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Facility {
+    Kern = 0,
+    User = 1,
+    Mail = 2,
+    Daemon = 3,
+    Auth = 4,
+    Syslog = 5,
+    Lpr = 6,
+    News = 7,
+    Uucp = 8,
+    Cron = 9,
+    AuthPriv = 10,
+    Ftp = 11,
+    Local0 = 16,
+    Local1 = 17,
+    Local2 = 18,
+    Local3 = 19,
+    Local4 = 20,
+    Local5 = 21,
+    Local6 = 22,
+    Local7 = 23,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyslogFormat {
+    Rfc5424,
+    Rfc3164,
+}
+
+#[derive(Clone, Debug)]
+pub struct SyslogSink {
+    pub path: PathBuf,
+    pub facility: Facility,
+    pub format: SyslogFormat,
+}
+
+impl SyslogSink {
+    pub fn new() -> Self {
+        Self {
+            path: PathBuf::from("/dev/log"),
+            facility: Facility::User,
+            format: SyslogFormat::Rfc5424,
+        }
+    }
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = path.into();
+        self
+    }
+    pub fn facility(mut self, facility: Facility) -> Self {
+        self.facility = facility;
+        self
+    }
+    pub fn format(mut self, format: SyslogFormat) -> Self {
+        self.format = format;
+        self
+    }
+    pub fn rfc3164(mut self) -> Self {
+        self.format = SyslogFormat::Rfc3164;
+        self
+    }
+}
+
+impl Default for SyslogSink {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) struct SyslogWriter {
+    sink: SyslogSink,
+    socket: UnixDatagram,
+    app_name: String,
+    hostname: String,
+    pid: u32,
+}
+
+pub(crate) struct SyslogMessage<'a> {
+    writer: &'a SyslogWriter,
+    severity: u8,
+}
+
+impl SyslogWriter {
+    pub(crate) fn connect(sink: SyslogSink, app_name: &str) -> io::Result<Self> {
+        let socket = UnixDatagram::unbound()?;
+        socket.connect(&sink.path)?;
+        Ok(Self {
+            sink,
+            socket,
+            app_name: normalize_app_name(app_name),
+            hostname: hostname(),
+            pid: std::process::id(),
+        })
+    }
+
+    fn header(&self, severity: u8, time: SystemTime) -> String {
+        let pri = (self.sink.facility as u8) * 8 + severity;
+        match self.sink.format {
+            SyslogFormat::Rfc5424 => format!(
+                "<{pri}>1 {} {} {} {} - - ",
+                rfc3339_utc(time, 6),
+                self.hostname,
+                self.app_name,
+                self.pid
+            ),
+            SyslogFormat::Rfc3164 => format!(
+                "<{pri}>{} {}[{}]: ",
+                bsd_timestamp(time, local_offset_secs(time).unwrap_or(0)),
+                self.app_name,
+                self.pid
+            ),
+        }
+    }
+
+    fn send(&self, severity: u8, buf: &[u8]) -> io::Result<()> {
+        let mut datagram = self.header(severity, SystemTime::now()).into_bytes();
+        datagram.extend_from_slice(buf.strip_suffix(b"\n").unwrap_or(buf));
+
+        match self.socket.send(&datagram) {
+            Ok(_) => Ok(()),
+            Err(_) => {
+                self.socket.connect(&self.sink.path)?;
+                self.socket.send(&datagram).map(|_| ())
+            }
+        }
+    }
+}
+
+impl Write for SyslogMessage<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.send(self.severity, buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for SyslogWriter {
+    type Writer = SyslogMessage<'a>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        SyslogMessage {
+            writer: self,
+            severity: severity(&Level::INFO),
+        }
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> Self::Writer {
+        SyslogMessage {
+            writer: self,
+            severity: severity(meta.level()),
+        }
+    }
+}
+
+fn severity(level: &Level) -> u8 {
+    match *level {
+        Level::ERROR => 3,
+        Level::WARN => 4,
+        Level::INFO => 6,
+        Level::DEBUG | Level::TRACE => 7,
+    }
+}
+
+fn normalize_app_name(name: &str) -> String {
+    if name.is_empty() {
+        return "-".to_string();
+    }
+    name.replace(char::is_whitespace, "_")
+}
+
+fn hostname() -> String {
+    let mut buf = [0u8; 256];
+    // SAFETY: the pointer and length describe `buf`, which outlives the call;
+    // `gethostname` writes at most `buf.len()` bytes into it.
+    if unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) } != 0 {
+        return "-".to_string();
+    }
+    // POSIX leaves the buffer without a NUL terminator when the name was
+    // truncated, so treat that the same as an unknown hostname.
+    let Some(len) = buf.iter().position(|&b| b == 0) else {
+        return "-".to_string();
+    };
+    match std::str::from_utf8(&buf[..len]) {
+        Ok(name) if !name.is_empty() => name.to_string(),
+        _ => "-".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn writer(sink: SyslogSink) -> (SyslogWriter, UnixDatagram) {
+        let (server, socket) = UnixDatagram::pair().unwrap();
+        let writer = SyslogWriter {
+            sink,
+            socket,
+            app_name: "app".to_string(),
+            hostname: "host".to_string(),
+            pid: 42,
+        };
+        (writer, server)
+    }
+
+    #[test]
+    fn formats_rfc5424_and_rfc3164_headers() {
+        let time = UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_000);
+        let (rfc5424, _server) = writer(SyslogSink::new().facility(Facility::Local3));
+        let (rfc3164, _server) = writer(SyslogSink::new().rfc3164());
+
+        assert_eq!(
+            rfc5424.header(severity(&Level::WARN), time),
+            "<156>1 2023-11-14T22:13:20.123456Z host app 42 - - "
+        );
+        assert!(
+            rfc3164
+                .header(severity(&Level::ERROR), time)
+                .starts_with("<11>")
+        );
+        assert!(rfc3164.header(3, time).ends_with(" app[42]: "));
+    }
+
+    #[test]
+    fn sends_one_datagram_per_event_without_trailing_newline() {
+        let (writer, server) = writer(SyslogSink::new());
+        writer.make_writer().write_all(b"first line\n").unwrap();
+
+        let mut buf = [0u8; 512];
+        let len = server.recv(&mut buf).unwrap();
+        let datagram = std::str::from_utf8(&buf[..len]).unwrap();
+        assert!(datagram.starts_with("<14>1 "), "{datagram}");
+        assert!(
+            datagram.ends_with(" host app 42 - - first line"),
+            "{datagram}"
+        );
+    }
+
+    #[test]
+    fn empty_app_name_becomes_nilvalue() {
+        assert_eq!(normalize_app_name(""), "-");
+        assert_eq!(normalize_app_name("my app"), "my_app");
+    }
+}
@@ -0,0 +1,291 @@
+use serde_json::json;
+use traxer::testing::{Level, capture};
+
+#[test]
+fn capture_records_plain_output_and_events() {
+    let cap = capture(
+        traxer::Config::new("traxer-capture")
+            .color(traxer::Color::Never)
+            .with_filter_directives("debug"),
+    )
+    .expect("capture should start");
+
+    traxer::error!(user_id = 42, "login failed");
+    traxer::debug!(attempt = 3, "retrying");
+    traxer::trace!("hidden");
+
+    assert_eq!(cap.lines().len(), 2, "output:\n{}", cap.output());
+    assert!(
+        cap.contains("[traxer-capture] ERROR login failed user_id=42"),
+        "output:\n{}",
+        cap.output()
+    );
+    cap.assert_event(Level::ERROR, &[("user_id", json!(42))]);
+    cap.assert_event(Level::DEBUG, &[("message", json!("retrying"))]);
+    cap.assert_no_event(Level::ERROR, &[("user_id", json!(7))]);
+    cap.assert_no_event(Level::TRACE, &[]);
+
+    let events = cap.events();
+    assert_eq!(events[0].message(), Some("login failed"));
+    assert_eq!(events[1].field("attempt"), Some(&json!(3)));
+}
+
+#[test]
+fn capture_records_json_output() {
+    let cap = capture(
+        traxer::Config::new("traxer-capture")
+            .json()
+            .with_filter_directives("info"),
+    )
+    .expect("capture should start");
+
+    traxer::warn!(path = "/tmp", "disk almost full");
+
+    let line: serde_json::Value = serde_json::from_str(&cap.lines()[0]).expect("invalid json line");
+    assert_eq!(line["level"], "WARN");
+    assert_eq!(line["path"], "/tmp");
+    cap.assert_event(Level::WARN, &[("path", json!("/tmp"))]);
+}
+
+#[test]
+fn capture_records_logfmt_output() {
+    let cap = capture(
+        traxer::Config::new("traxer-capture")
+            .logfmt()
+            .with_base_field("service", "cli")
+            .with_filter_directives("info"),
+    )
+    .expect("capture should start");
+
+    traxer::info!(user_id = 42, query = "a b", "request done");
+
+    let line = &cap.lines()[0];
+    assert!(line.starts_with("ts="), "line: {line}");
+    assert!(
+        line.contains(
+            " level=info name=traxer-capture msg=\"request done\" user_id=42 query=\"a b\" service=cli"
+        ),
+        "line: {line}"
+    );
+}
+
+#[test]
+fn capture_prefixes_plain_lines_with_configured_time() {
+    let utc = capture(
+        traxer::Config::new("traxer-capture")
+            .color(traxer::Color::Never)
+            .time_style(traxer::TimeStyle::Utc)
+            .with_filter_directives("info"),
+    )
+    .expect("capture should start");
+    traxer::info!("utc");
+    let line = &utc.lines()[0];
+    let (ts, rest) = line.split_once(' ').expect("missing timestamp");
+    assert_eq!(ts.len(), "2026-01-01T00:00:00.000Z".len(), "line: {line}");
+    assert!(ts.ends_with('Z'), "line: {line}");
+    assert_eq!(rest, "[traxer-capture] INFO utc");
+    drop(utc);
+
+    let uptime = capture(
+        traxer::Config::new("traxer-capture")
+            .color(traxer::Color::Never)
+            .time_style(traxer::TimeStyle::Uptime)
+            .with_filter_directives("info"),
+    )
+    .expect("capture should start");
+    traxer::info!("uptime");
+    let line = &uptime.lines()[0];
+    assert!(line.starts_with("   0."), "line: {line}");
+    assert!(
+        line.ends_with("s [traxer-capture] INFO uptime"),
+        "line: {line}"
+    );
+}
+
+#[test]
+fn capture_encodes_json_timestamp_with_configured_key() {
+    let cap = capture(
+        traxer::Config::new("traxer-capture")
+            .json()
+            .timestamp_format(traxer::TimestampFormat::Rfc3339)
+            .timestamp_key("@timestamp")
+            .with_filter_directives("info"),
+    )
+    .expect("capture should start");
+    traxer::info!("rfc3339");
+    let line: serde_json::Value = serde_json::from_str(&cap.lines()[0]).expect("invalid json line");
+    let ts = line["@timestamp"]
+        .as_str()
+        .expect("timestamp should be a string");
+    assert_eq!(ts.len(), "2026-01-01T00:00:00.000000000Z".len(), "ts: {ts}");
+    assert!(line.get("timestamp").is_none());
+    drop(cap);
+
+    let cap = capture(
+        traxer::Config::new("traxer-capture")
+            .json()
+            .timestamp_format(traxer::TimestampFormat::UnixSeconds)
+            .with_filter_directives("info"),
+    )
+    .expect("capture should start");
+    traxer::info!("seconds");
+    let line: serde_json::Value = serde_json::from_str(&cap.lines()[0]).expect("invalid json line");
+    assert!(line["timestamp"].is_f64(), "line: {line}");
+}
+
+#[test]
+fn capture_includes_span_stack_in_json() {
+    let cap = capture(
+        traxer::Config::new("traxer-capture")
+            .json()
+            .span(true)
+            .flatten_span_fields(true)
+            .with_filter_directives("info"),
+    )
+    .expect("capture should start");
+
+    let request = tracing::info_span!("request", request_id = 7, user = tracing::field::Empty);
+    let _request = request.enter();
+    request.record("user", "alice");
+    let parse = tracing::info_span!("parse", n = 3);
+    let _parse = parse.enter();
+    traxer::info!(n = 4, "parsed");
+
+    let line: serde_json::Value = serde_json::from_str(&cap.lines()[0]).expect("invalid json line");
+    assert_eq!(line["span"], "parse");
+    assert_eq!(
+        line["spans"],
+        json!([
+            {"name": "request", "request_id": 7, "user": "alice"},
+            {"name": "parse", "n": 3},
+        ])
+    );
+    assert_eq!(line["request_id"], 7);
+    assert_eq!(line["user"], "alice");
+    assert_eq!(line["n"], 4);
+}
+
+#[test]
+fn capture_keeps_span_name_and_a_span_field_called_name() {
+    let cap = capture(
+        traxer::Config::new("traxer-capture")
+            .json()
+            .span(true)
+            .with_filter_directives("info"),
+    )
+    .expect("capture should start");
+
+    let span = tracing::info_span!("load", name = "config.toml");
+    let _span = span.enter();
+    traxer::info!("loaded");
+
+    let line: serde_json::Value = serde_json::from_str(&cap.lines()[0]).expect("invalid json line");
+    assert_eq!(
+        line["spans"],
+        json!([{"name": "load", "span.name": "config.toml"}])
+    );
+}
+
+#[test]
+fn capture_renders_span_path_in_plain_output() {
+    let cap = capture(
+        traxer::Config::new("traxer-capture")
+            .color(traxer::Color::Never)
+            .span_path(true)
+            .with_filter_directives("info"),
+    )
+    .expect("capture should start");
+
+    let fetch = tracing::info_span!("fetch", url = "https://example.com");
+    let _fetch = fetch.enter();
+    let parse = tracing::info_span!("parse", n = 3);
+    let _parse = parse.enter();
+    let bare = tracing::info_span!("bare");
+    let _bare = bare.enter();
+    traxer::info!("parsed");
+
+    assert_eq!(
+        cap.lines()[0],
+        "[traxer-capture] INFO fetch{url=\"https://example.com\"}:parse{n=3}:bare parsed"
+    );
+}
+
+#[test]
+fn capture_includes_source_location_when_enabled() {
+    let cap = capture(
+        traxer::Config::new("traxer-capture")
+            .json()
+            .with_target()
+            .with_module_path()
+            .with_file()
+            .with_line()
+            .with_filter_directives("info"),
+    )
+    .expect("capture should start");
+    traxer::info!("located");
+    let line: serde_json::Value = serde_json::from_str(&cap.lines()[0]).expect("invalid json line");
+    assert_eq!(line["target"], "capture");
+    assert_eq!(line["module_path"], "capture");
+    assert!(
+        line["file"]
+            .as_str()
+            .is_some_and(|file| file.ends_with("capture.rs")),
+        "line: {line}"
+    );
+    assert!(line["line"].is_u64(), "line: {line}");
+    drop(cap);
+
+    let cap = capture(
+        traxer::Config::new("traxer-capture")
+            .color(traxer::Color::Never)
+            .with_target()
+            .with_filter_directives("info"),
+    )
+    .expect("capture should start");
+    traxer::info!("located");
+    assert_eq!(
+        cap.lines()[0],
+        "[traxer-capture] INFO located target=capture"
+    );
+}
+
+#[test]
+fn capture_includes_thread_name_and_id_when_enabled() {
+    let handle = std::thread::Builder::new()
+        .name("worker-1".to_string())
+        .spawn(|| {
+            let cap = capture(
+                traxer::Config::new("traxer-capture")
+                    .color(traxer::Color::Never)
+                    .with_thread_name()
+                    .with_thread_id()
+                    .with_filter_directives("info"),
+            )
+            .expect("capture should start");
+            traxer::info!("from worker");
+            cap.lines()[0].clone()
+        })
+        .expect("failed to spawn thread");
+    let line = handle.join().expect("worker panicked");
+    assert!(
+        line.starts_with("[traxer-capture] [worker-1#"),
+        "line: {line}"
+    );
+    assert!(line.ends_with("] INFO from worker"), "line: {line}");
+
+    let cap = capture(
+        traxer::Config::new("traxer-capture")
+            .json()
+            .with_thread_name()
+            .with_thread_id()
+            .with_filter_directives("info"),
+    )
+    .expect("capture should start");
+    traxer::info!("from test thread");
+    let line: serde_json::Value = serde_json::from_str(&cap.lines()[0]).expect("invalid json line");
+    assert!(line["thread_name"].is_string(), "line: {line}");
+    assert!(
+        line["thread_id"].as_u64().is_some_and(|id| id > 0),
+        "line: {line}"
+    );
+}
@@ -12,11 +12,16 @@ fn config_defaults_match_cli_expectations() {
     assert_eq!(cfg.quiet, 0);
     assert!(cfg.filter_directives.is_none());
     assert!(cfg.base_fields.is_empty());
+    assert!(cfg.outputs.is_empty());
+    assert_eq!(cfg.time_style, traxer::TimeStyle::None);
     assert!(!cfg.include_pid);
     assert!(!cfg.include_exe);
     assert!(!cfg.include_version);
     assert!(!cfg.span);
     assert!(cfg.error_report);
+    assert_eq!(cfg.report_hook.theme, traxer::ReportTheme::Dark);
+    assert!(cfg.report_hook.env_section);
+    assert!(cfg.report_hook.location_section);
 }
 
 #[test]
@@ -99,3 +104,19 @@ fn config_accepts_custom_tty_and_non_tty_policies() {
     ));
     assert_eq!(assigned.non_tty.include_pid, Some(true));
 }
+
+#[test]
+fn file_sink_builder_sets_rotation_options() {
+    let sink = traxer::FileSink::new("logs/app.log")
+        .max_size(1024)
+        .daily()
+        .keep(3);
+
+    assert_eq!(sink.path, std::path::PathBuf::from("logs/app.log"));
+    assert_eq!(sink.max_size, Some(1024));
+    assert_eq!(sink.rotation, traxer::Rotation::Daily);
+    assert_eq!(sink.keep, 3);
+
+    let cfg = traxer::Config::new("traxer").stream(traxer::Stream::File(sink));
+    assert!(matches!(cfg.stream, traxer::Stream::File(_)));
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use common::SharedBuffer;
+
+#[test]
+fn custom_stream_routes_output_to_make_writer() {
+    let buffer = SharedBuffer::default();
+    let writer = buffer.clone();
+    let cfg = traxer::Config::new("traxer-custom")
+        .with_filter_directives("info")
+        .policy(traxer::Policy::default_auto())
+        .stream(traxer::Stream::custom(move || writer.clone()));
+
+    {
+        let _guard = traxer::set_default(cfg).expect("set_default should succeed");
+        traxer::info!(user_id = 42, "custom message");
+    }
+
+    let output = buffer.contents();
+    let line: serde_json::Value = serde_json::from_str(output.trim()).expect("invalid json line");
+    assert_eq!(line["name"], "traxer-custom");
+    assert_eq!(line["user_id"], 42);
+    assert_eq!(line["message"], "custom message");
+}
+
+#[test]
+fn custom_stream_tty_flag_selects_tty_policy() {
+    let buffer = SharedBuffer::default();
+    let writer = buffer.clone();
+    let cfg = traxer::Config::new("traxer-custom")
+        .with_filter_directives("info")
+        .policy(traxer::Policy::default_auto())
+        .color(traxer::Color::Never)
+        .stream(traxer::Stream::Custom(
+            traxer::CustomWriter::new(move || writer.clone()).tty(true),
+        ));
+
+    {
+        let _guard = traxer::set_default(cfg).expect("set_default should succeed");
+        traxer::info!("tty message");
+    }
+
+    let output = buffer.contents();
+    assert!(output.starts_with("[traxer-custom] "), "output:\n{output}");
+    assert!(output.contains("tty message"), "output:\n{output}");
+}
+
+#[test]
+fn span_path_fields_follow_each_output_color_setting() {
+    let colored = SharedBuffer::default();
+    let uncolored = SharedBuffer::default();
+    let (colored_writer, uncolored_writer) = (colored.clone(), uncolored.clone());
+    let cfg = traxer::Config::new("traxer-custom")
+        .with_filter_directives("info")
+        .span_path(true)
+        .color(traxer::Color::Always)
+        .stream(traxer::Stream::custom(move || colored_writer.clone()))
+        .with_output(
+            traxer::Output::new(traxer::Stream::custom(move || uncolored_writer.clone()))
+                .color(traxer::Color::Never),
+        );
+
+    {
+        let _guard = traxer::set_default(cfg).expect("set_default should succeed");
+        let span = tracing::info_span!("fetch", url = "x");
+        let _span = span.enter();
+        traxer::info!("fetched");
+    }
+
+    let colored = colored.contents();
+    let uncolored = uncolored.contents();
+    assert!(
+        colored.contains("fetch{\x1b[3murl\x1b[0m\x1b[2m=\x1b[0m\"x\"}"),
+        "output:\n{colored:?}"
+    );
+    assert_eq!(uncolored, "[traxer-custom] INFO fetch{url=\"x\"} fetched\n");
+}
@@ -2,6 +2,15 @@ use serde_json::Value as JsonValue;
 use std::process::Command;
 
 fn run_example(example_name: &str, rust_log: Option<&str>, args: &[&str]) -> String {
+    let (stdout, stderr) = run_example_streams(example_name, rust_log, args);
+    format!("{stdout}{stderr}")
+}
+
+fn run_example_streams(
+    example_name: &str,
+    rust_log: Option<&str>,
+    args: &[&str],
+) -> (String, String) {
     let mut command = Command::new("cargo");
     command
         .args(["run", "--quiet", "--example", example_name])
@@ -33,7 +42,7 @@ fn run_example(example_name: &str, rust_log: Option<&str>, args: &[&str]) -> Str
         stderr
     );
 
-    format!("{stdout}{stderr}")
+    (stdout, stderr)
 }
 
 fn strip_ansi(input: &str) -> String {
@@ -140,3 +149,16 @@ fn try_init_example_is_idempotent() {
 
     assert!(output.contains("try_init ok"), "output:\n{output}");
 }
+
+#[test]
+fn split_example_routes_warnings_and_errors_to_stderr() {
+    let (stdout, stderr) = run_example_streams("split", None, &[]);
+
+    assert!(stdout.contains("INFO info message"), "stdout:\n{stdout}");
+    assert!(!stdout.contains("warn message"), "stdout:\n{stdout}");
+    assert!(!stdout.contains("error message"), "stdout:\n{stdout}");
+
+    assert!(stderr.contains("ERROR error message"), "stderr:\n{stderr}");
+    assert!(stderr.contains("WARN warn message"), "stderr:\n{stderr}");
+    assert!(!stderr.contains("info message"), "stderr:\n{stderr}");
+}
@@ -0,0 +1,33 @@
+#![cfg(unix)]
+
+use std::{io::Read, os::fd::AsRawFd};
+
+#[test]
+fn fd_stream_writes_to_inherited_descriptor() {
+    let (mut reader, writer) = std::io::pipe().expect("pipe should open");
+    let cfg = traxer::Config::new("traxer-fd")
+        .with_filter_directives("info")
+        .policy(traxer::Policy::default_auto())
+        .stream(traxer::Stream::Fd(writer.as_raw_fd()));
+
+    {
+        let _guard = traxer::set_default(cfg).expect("set_default should succeed");
+        traxer::info!(step = "compile", "fd message");
+    }
+    drop(writer);
+
+    let mut output = String::new();
+    reader.read_to_string(&mut output).expect("read pipe");
+    let line: serde_json::Value = serde_json::from_str(output.trim()).expect("invalid json line");
+    assert_eq!(line["name"], "traxer-fd");
+    assert_eq!(line["step"], "compile");
+    assert_eq!(line["message"], "fd message");
+}
+
+#[test]
+fn fd_stream_reports_closed_descriptor() {
+    let cfg = traxer::Config::new("traxer-fd").stream(traxer::Stream::Fd(-1));
+
+    let err = traxer::set_default(cfg).expect_err("closed fd should fail");
+    assert!(matches!(err, traxer::InitError::OpenLogFd(_)), "{err}");
+}
@@ -0,0 +1,121 @@
+#![cfg(unix)]
+
+use std::{collections::HashMap, os::unix::net::UnixDatagram, time::Duration};
+
+fn parse(datagram: &[u8]) -> HashMap<String, String> {
+    let mut fields = HashMap::new();
+    let mut rest = datagram;
+    while !rest.is_empty() {
+        let end = rest.iter().position(|&b| b == b'\n' || b == b'=').unwrap();
+        let name = String::from_utf8(rest[..end].to_vec()).unwrap();
+        let value;
+        if rest[end] == b'=' {
+            let newline = rest[end..].iter().position(|&b| b == b'\n').unwrap() + end;
+            value = rest[end + 1..newline].to_vec();
+            rest = &rest[newline + 1..];
+        } else {
+            let len = u64::from_le_bytes(rest[end + 1..end + 9].try_into().unwrap()) as usize;
+            value = rest[end + 9..end + 9 + len].to_vec();
+            rest = &rest[end + 9 + len + 1..];
+        }
+        fields.insert(name, String::from_utf8(value).unwrap());
+    }
+    fields
+}
+
+#[test]
+fn journald_stream_sends_native_protocol_fields() {
+    let path = std::env::temp_dir().join(format!("traxer-journald-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let server = UnixDatagram::bind(&path).expect("bind journald stand-in");
+    server
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .expect("set timeout");
+
+    let cfg = traxer::Config::new("traxer-unit")
+        .with_filter_directives("info")
+        .with_base_field("service", "api")
+        .with_line()
+        .stream(traxer::Stream::Journald(
+            traxer::JournaldSink::new().path(&path),
+        ));
+
+    {
+        let _guard = traxer::set_default(cfg).expect("set_default should succeed");
+        traxer::warn!(user_id = 42, detail = "first\nsecond", "quota exceeded");
+    }
+
+    let mut buf = [0u8; 4096];
+    let len = server.recv(&mut buf).expect("datagram");
+    let fields = parse(&buf[..len]);
+    assert_eq!(fields["PRIORITY"], "4");
+    assert_eq!(fields["SYSLOG_IDENTIFIER"], "traxer-unit");
+    assert_eq!(fields["MESSAGE"], "quota exceeded");
+    assert_eq!(fields["USER_ID"], "42");
+    assert_eq!(fields["DETAIL"], "first\nsecond");
+    assert_eq!(fields["SERVICE"], "api");
+    assert!(fields["CODE_LINE"].parse::<u32>().is_ok());
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn journald_prefixes_fields_that_collide_with_reserved_names() {
+    let path = std::env::temp_dir().join(format!(
+        "traxer-journald-reserved-{}.sock",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    let server = UnixDatagram::bind(&path).expect("bind journald stand-in");
+    server
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .expect("set timeout");
+
+    let cfg = traxer::Config::new("traxer-unit")
+        .with_filter_directives("info")
+        .flatten_span_fields(true)
+        .stream(traxer::Stream::Journald(
+            traxer::JournaldSink::new().path(&path),
+        ));
+
+    {
+        let _guard = traxer::set_default(cfg).expect("set_default should succeed");
+        let span = tracing::info_span!("job", syslog_identifier = "spoofed");
+        let _span = span.enter();
+        traxer::warn!(priority = "high", "quota exceeded");
+    }
+
+    let mut buf = [0u8; 4096];
+    let len = server.recv(&mut buf).expect("datagram");
+    let fields = parse(&buf[..len]);
+    assert_eq!(fields["PRIORITY"], "4");
+    assert_eq!(fields["F_PRIORITY"], "high");
+    assert_eq!(fields["SYSLOG_IDENTIFIER"], "traxer-unit");
+    assert_eq!(fields["F_SYSLOG_IDENTIFIER"], "spoofed");
+    assert_eq!(fields["MESSAGE"], "quota exceeded");
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn journald_counts_events_it_cannot_send() {
+    let path =
+        std::env::temp_dir().join(format!("traxer-journald-gone-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let server = UnixDatagram::bind(&path).expect("bind journald stand-in");
+
+    let cfg = traxer::Config::new("traxer-unit")
+        .with_filter_directives("info")
+        .stream(traxer::Stream::Journald(
+            traxer::JournaldSink::new().path(&path),
+        ));
+
+    let (guard, handle) = traxer::set_default(cfg).expect("set_default should succeed");
+    drop(server);
+    let _ = std::fs::remove_file(&path);
+    traxer::info!("nobody is listening");
+    traxer::info!("still nobody");
+    drop(guard);
+
+    assert_eq!(handle.dropped_events(), 2);
+}
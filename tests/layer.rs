@@ -0,0 +1,55 @@
+use std::{
+    fs,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+use tracing_subscriber::{Layer, layer::SubscriberExt};
+
+struct CountingLayer(Arc<AtomicUsize>);
+
+impl<S: tracing::Subscriber> Layer<S> for CountingLayer {
+    fn on_event(
+        &self,
+        _event: &tracing::Event<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn layer_composes_with_other_layers() {
+    // SAFETY: this is the only test in this binary, so no other thread reads
+    // the environment concurrently.
+    unsafe { std::env::remove_var("RUST_LOG") };
+    let dir = std::env::temp_dir().join(format!("traxer-layer-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("app.log");
+
+    let cfg = traxer::Config::new("traxer-layer")
+        .with_filter_directives("info")
+        .stream(traxer::Stream::File(traxer::FileSink::new(&path)));
+    let count = Arc::new(AtomicUsize::new(0));
+    let (layer, _handle) = traxer::layer(cfg).expect("layer should build");
+    let subscriber = tracing_subscriber::registry()
+        .with(layer)
+        .with(CountingLayer(count.clone()));
+
+    tracing::subscriber::with_default(subscriber, || {
+        traxer::info!("composed message");
+        traxer::debug!("filtered message");
+    });
+
+    let output = fs::read_to_string(&path).expect("log file should exist");
+    assert!(
+        output.contains("[traxer-layer] INFO composed message"),
+        "output:\n{output}"
+    );
+    assert!(!output.contains("filtered message"), "output:\n{output}");
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+    assert!(!traxer::is_initialized());
+
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "log")]
+
+mod common;
+
+use common::SharedBuffer;
+
+#[test]
+fn log_records_are_formatted_and_filtered() {
+    // SAFETY: this is the only test in this binary, so no other thread reads
+    // the environment concurrently.
+    unsafe { std::env::remove_var("RUST_LOG") };
+    let buffer = SharedBuffer::default();
+    let writer = buffer.clone();
+    let cfg = traxer::Config::new("traxer-log")
+        .error_report(false)
+        .with_filter_directives("info,noisy_dep=warn")
+        .policy(traxer::Policy::default_auto())
+        .with_target()
+        .stream(traxer::Stream::custom(move || writer.clone()));
+    traxer::try_init(cfg).expect("try_init should succeed");
+
+    log::info!(target: "legacy_dep", "hello from log");
+    log::info!(target: "noisy_dep", "filtered by target");
+    log::debug!(target: "legacy_dep", "filtered by level");
+    tracing::info!(target: "native", message = "native event", "log.path" = "/var/log/app.log");
+
+    let output = buffer.contents();
+    let lines: Vec<serde_json::Value> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("invalid json line"))
+        .collect();
+    assert_eq!(lines.len(), 2, "output:\n{output}");
+    assert_eq!(lines[0]["message"], "hello from log");
+    assert_eq!(lines[0]["level"], "INFO");
+    assert_eq!(lines[0]["target"], "legacy_dep");
+    assert!(lines[0].get("log.target").is_none(), "output:\n{output}");
+    assert_eq!(lines[1]["message"], "native event");
+    assert_eq!(lines[1]["log.path"], "/var/log/app.log");
+}
@@ -0,0 +1,31 @@
+use std::fs;
+
+#[test]
+fn non_blocking_writer_flushes_when_handle_drops() {
+    let dir = std::env::temp_dir().join(format!("traxer-non-blocking-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("app.log");
+
+    let cfg = traxer::Config::new("traxer-non-blocking")
+        .error_report(false)
+        .with_filter_directives("info")
+        .non_blocking(traxer::NonBlocking::new(16))
+        .stream(traxer::Stream::File(traxer::FileSink::new(&path)));
+    let handle = traxer::try_init(cfg).expect("try_init should succeed");
+
+    for index in 0..1000 {
+        traxer::info!(index, "queued line");
+    }
+    assert_eq!(handle.dropped_events(), 0);
+    drop(handle);
+
+    let output = fs::read_to_string(&path).expect("log file should exist");
+    assert_eq!(output.lines().count(), 1000);
+    assert!(
+        output.ends_with("queued line index=999\n"),
+        "tail:\n{}",
+        &output[output.len() - 100..]
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
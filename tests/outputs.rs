@@ -0,0 +1,46 @@
+use serde_json::Value as JsonValue;
+use std::fs;
+
+#[test]
+fn outputs_use_independent_format_and_filter() {
+    // SAFETY: this is the only test in this binary, so no other thread reads
+    // the environment concurrently.
+    unsafe { std::env::remove_var("RUST_LOG") };
+    let dir = std::env::temp_dir().join(format!("traxer-outputs-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let plain_path = dir.join("plain.log");
+    let json_path = dir.join("json.log");
+
+    let cfg = traxer::Config::new("traxer-outputs")
+        .error_report(false)
+        .with_filter_directives("info")
+        .stream(traxer::Stream::File(traxer::FileSink::new(&plain_path)))
+        .with_output(
+            traxer::Output::new(traxer::Stream::File(traxer::FileSink::new(&json_path)))
+                .json()
+                .with_filter_directives("debug"),
+        );
+    traxer::try_init(cfg).expect("try_init should succeed");
+
+    traxer::info!(user_id = 42, "info message");
+    traxer::debug!("debug message");
+
+    let plain = fs::read_to_string(&plain_path).expect("plain log should exist");
+    assert!(plain.contains("[traxer-outputs] INFO"), "plain:\n{plain}");
+    assert!(plain.contains("info message"), "plain:\n{plain}");
+    assert!(!plain.contains("debug message"), "plain:\n{plain}");
+
+    let json = fs::read_to_string(&json_path).expect("json log should exist");
+    let lines: Vec<JsonValue> = json
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("failed to parse json log line"))
+        .collect();
+    assert_eq!(lines.len(), 2, "json:\n{json}");
+    assert_eq!(lines[0]["user_id"], JsonValue::Number(42.into()));
+    assert_eq!(
+        lines[1]["message"],
+        JsonValue::String("debug message".to_string())
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use common::SharedBuffer;
+
+#[test]
+fn json_mode_reports_panics_as_single_events() {
+    let buffer = SharedBuffer::default();
+    let writer = buffer.clone();
+    let cfg = traxer::Config::new("traxer-panic")
+        .json()
+        .with_filter_directives("info")
+        .stream(traxer::Stream::custom(move || writer.clone()));
+    traxer::try_init(cfg).expect("try_init should succeed");
+
+    let result = std::thread::Builder::new()
+        .name("worker".to_string())
+        .spawn(|| panic!("disk on fire: {}", 42))
+        .expect("spawn worker")
+        .join();
+    assert!(result.is_err());
+
+    let output = buffer.contents();
+    let lines: Vec<serde_json::Value> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("invalid json line"))
+        .collect();
+    assert_eq!(lines.len(), 1, "output:\n{output}");
+    assert_eq!(lines[0]["level"], "ERROR");
+    assert_eq!(lines[0]["message"], "disk on fire: 42");
+    assert_eq!(lines[0]["thread"], "worker");
+    assert!(
+        lines[0]["location"]
+            .as_str()
+            .is_some_and(|location| location.starts_with("tests/panic_json.rs:")),
+        "output:\n{output}"
+    );
+}
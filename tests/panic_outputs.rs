@@ -0,0 +1,39 @@
+mod common;
+
+use common::SharedBuffer;
+
+#[test]
+fn json_extra_output_reports_panics_despite_target_only_directives() {
+    let plain = SharedBuffer::default();
+    let json = SharedBuffer::default();
+    let (plain_writer, json_writer) = (plain.clone(), json.clone());
+    let cfg = traxer::Config::new("traxer-panic")
+        .error_report(false)
+        .with_filter_directives("panic_outputs=info")
+        .stream(traxer::Stream::custom(move || plain_writer.clone()))
+        .with_output(
+            traxer::Output::new(traxer::Stream::custom(move || json_writer.clone()))
+                .json()
+                .with_filter_directives("panic_outputs=info"),
+        );
+    let _handle = traxer::try_init(cfg).expect("try_init should succeed");
+
+    let result = std::thread::Builder::new()
+        .name("worker".to_string())
+        .spawn(|| panic!("disk on fire"))
+        .expect("spawn worker")
+        .join();
+    assert!(result.is_err());
+
+    let output = json.contents();
+    let lines: Vec<serde_json::Value> = output
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("invalid json line"))
+        .collect();
+    assert_eq!(lines.len(), 1, "output:\n{output}");
+    assert_eq!(lines[0]["message"], "disk on fire");
+    assert_eq!(lines[0]["thread"], "worker");
+
+    let plain = plain.contents();
+    assert!(plain.contains("ERROR disk on fire"), "output:\n{plain}");
+}
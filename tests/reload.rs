@@ -0,0 +1,52 @@
+use std::fs;
+
+#[test]
+fn handle_reloads_filter_after_try_init() {
+    // SAFETY: this is the only test in this binary, so no other thread reads
+    // the environment concurrently.
+    unsafe { std::env::remove_var("RUST_LOG") };
+    let dir = std::env::temp_dir().join(format!("traxer-reload-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("app.log");
+
+    let cfg = traxer::Config::new("traxer-reload")
+        .error_report(false)
+        .stream(traxer::Stream::File(traxer::FileSink::new(&path)));
+    let handle = traxer::try_init(cfg.clone()).expect("try_init should succeed");
+    assert_eq!(handle.verbose(), 0);
+
+    traxer::debug!("debug before");
+    handle.set_verbose(1).expect("set_verbose should succeed");
+    traxer::debug!("debug after verbose");
+    traxer::trace!("trace after verbose");
+
+    let again = traxer::try_init(cfg).expect("second try_init should be a no-op");
+    assert_eq!(again.verbose(), 1);
+
+    again.set_quiet(1).expect("set_quiet should succeed");
+    traxer::info!("info after quiet");
+    traxer::warn!("warn after quiet");
+
+    handle
+        .set_filter_directives("reload=trace")
+        .expect("set_filter_directives should succeed");
+    traxer::trace!("trace after directives");
+    assert!(matches!(
+        handle.set_filter_directives("reload=[bad"),
+        Err(traxer::InitError::InvalidFilterDirectives(_))
+    ));
+    assert_eq!(handle.filter_directives().as_deref(), Some("reload=trace"));
+
+    let output = fs::read_to_string(&path).expect("log file should exist");
+    assert!(!output.contains("debug before"), "output:\n{output}");
+    assert!(output.contains("debug after verbose"), "output:\n{output}");
+    assert!(!output.contains("trace after verbose"), "output:\n{output}");
+    assert!(!output.contains("info after quiet"), "output:\n{output}");
+    assert!(output.contains("warn after quiet"), "output:\n{output}");
+    assert!(
+        output.contains("trace after directives"),
+        "output:\n{output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
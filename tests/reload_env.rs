@@ -0,0 +1,37 @@
+use std::fs;
+
+#[test]
+fn handle_starts_from_rust_log_directives() {
+    let dir = std::env::temp_dir().join(format!("traxer-reload-env-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("app.log");
+
+    // SAFETY: this is the only test in this binary, so no other thread reads
+    // the environment concurrently.
+    unsafe { std::env::set_var("RUST_LOG", "reload_env=trace") };
+    let cfg = traxer::Config::new("traxer-reload-env")
+        .error_report(false)
+        .stream(traxer::Stream::File(traxer::FileSink::new(&path)));
+    let handle = traxer::try_init(cfg).expect("try_init should succeed");
+    assert_eq!(
+        handle.filter_directives().as_deref(),
+        Some("reload_env=trace")
+    );
+
+    handle
+        .increase_verbosity()
+        .expect("increase_verbosity should succeed");
+    handle
+        .decrease_verbosity()
+        .expect("decrease_verbosity should succeed");
+    assert_eq!(
+        handle.filter_directives().as_deref(),
+        Some("reload_env=trace")
+    );
+    traxer::trace!("trace after stepping");
+
+    let output = fs::read_to_string(&path).expect("log file should exist");
+    assert!(output.contains("trace after stepping"), "output:\n{output}");
+
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,143 @@
+use color_eyre::eyre::{WrapErr, eyre};
+use serde_json::json;
+use std::{error::Error, fmt};
+use traxer::testing::{Level, capture};
+
+#[derive(Debug)]
+struct ConfigError {
+    source: std::io::Error,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("failed to load config")
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[test]
+fn report_error_logs_message_and_source_chain() {
+    let cap = capture(
+        traxer::Config::new("traxer-report")
+            .json()
+            .with_filter_directives("info"),
+    )
+    .expect("capture should start");
+
+    let err = ConfigError {
+        source: std::io::Error::new(std::io::ErrorKind::NotFound, "config.toml missing"),
+    };
+    traxer::report_error(&err);
+    traxer::report_error!(std::io::Error::other("standalone"));
+
+    cap.assert_event(
+        Level::ERROR,
+        &[
+            ("message", json!("failed to load config")),
+            ("error.message", json!("failed to load config")),
+            ("error.chain", json!(["config.toml missing"])),
+        ],
+    );
+    let line: serde_json::Value =
+        serde_json::from_str(cap.lines()[0].as_str()).expect("invalid json line");
+    assert_eq!(line["error.chain"], json!(["config.toml missing"]));
+
+    let events = cap.events();
+    assert_eq!(events[1].field("error.message"), Some(&json!("standalone")));
+    assert_eq!(events[1].field("error.chain"), None);
+}
+
+#[test]
+fn report_eyre_logs_wrapped_context_chain() {
+    let cap = capture(
+        traxer::Config::new("traxer-report")
+            .json()
+            .with_filter_directives("info"),
+    )
+    .expect("capture should start");
+
+    let report = Err::<(), _>(eyre!("connection refused"))
+        .wrap_err("failed to reach registry")
+        .wrap_err("deploy failed")
+        .unwrap_err();
+    traxer::report_eyre!(report);
+
+    cap.assert_event(
+        Level::ERROR,
+        &[
+            ("error.message", json!("deploy failed")),
+            (
+                "error.chain",
+                json!(["failed to reach registry", "connection refused"]),
+            ),
+        ],
+    );
+}
+
+#[test]
+fn report_error_renders_source_chain_in_plain_and_logfmt() {
+    let err = ConfigError {
+        source: std::io::Error::new(std::io::ErrorKind::NotFound, "config.toml missing"),
+    };
+
+    let plain = capture(
+        traxer::Config::new("traxer-report")
+            .color(traxer::Color::Never)
+            .with_filter_directives("info"),
+    )
+    .expect("capture should start");
+    traxer::report_error(&err);
+    assert_eq!(
+        plain.lines()[0],
+        "[traxer-report] ERROR failed to load config error.message=failed to load config error.chain=[config.toml missing]"
+    );
+    drop(plain);
+
+    let logfmt = capture(
+        traxer::Config::new("traxer-report")
+            .logfmt()
+            .with_filter_directives("info"),
+    )
+    .expect("capture should start");
+    traxer::report_error(&err);
+    let line = &logfmt.lines()[0];
+    assert!(
+        line.ends_with(
+            " msg=\"failed to load config\" error.message=\"failed to load config\" error.chain=\"[config.toml missing]\""
+        ),
+        "line: {line}"
+    );
+}
+
+#[test]
+fn report_macros_record_the_call_site() {
+    let cap = capture(
+        traxer::Config::new("traxer-report")
+            .json()
+            .with_target()
+            .with_file()
+            .with_filter_directives("report_error=info"),
+    )
+    .expect("capture should start");
+
+    traxer::report_error!(std::io::Error::other("standalone"));
+    traxer::report_eyre!(eyre!("wrapped"));
+
+    let lines = cap.lines();
+    assert_eq!(lines.len(), 2, "output:\n{}", cap.output());
+    for line in lines {
+        let line: serde_json::Value = serde_json::from_str(&line).expect("invalid json line");
+        assert_eq!(line["target"], "report_error");
+        assert!(
+            line["file"]
+                .as_str()
+                .is_some_and(|file| file.ends_with("report_error.rs")),
+            "line: {line}"
+        );
+    }
+}
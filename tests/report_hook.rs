@@ -0,0 +1,24 @@
+use color_eyre::eyre::eyre;
+
+#[test]
+fn report_hook_controls_theme_and_sections() {
+    let cfg = traxer::Config::new("traxer-report")
+        .plain()
+        .with_filter_directives("info")
+        .stream(traxer::Stream::custom(std::io::sink))
+        .report_hook(
+            traxer::ReportHook::new()
+                .blank()
+                .env_section(false)
+                .location_section(false)
+                .hide_frames("std::"),
+        );
+    traxer::try_init(cfg).expect("try_init should succeed");
+
+    let rendered = format!("{:?}", eyre!("deploy failed"));
+
+    assert!(rendered.contains("deploy failed"), "report:\n{rendered}");
+    assert!(!rendered.contains('\x1b'), "report:\n{rendered}");
+    assert!(!rendered.contains("Location:"), "report:\n{rendered}");
+    assert!(!rendered.contains("RUST_BACKTRACE"), "report:\n{rendered}");
+}
@@ -0,0 +1,52 @@
+use std::{fs, path::PathBuf};
+
+fn temp_log(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("traxer-scoped-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir.join("app.log")
+}
+
+#[test]
+fn set_default_applies_plain_config_to_current_thread() {
+    let path = temp_log("plain");
+    let cfg = traxer::Config::new("traxer-scoped-plain")
+        .with_filter_directives("info")
+        .span(true)
+        .stream(traxer::Stream::File(traxer::FileSink::new(&path)));
+
+    {
+        let _guard = traxer::set_default(cfg).expect("set_default should succeed");
+        let span = tracing::info_span!("work");
+        let _entered = span.enter();
+        traxer::info!("scoped plain");
+    }
+    traxer::info!("after guard");
+
+    let output = fs::read_to_string(&path).expect("log file should exist");
+    assert!(
+        output.contains("[traxer-scoped-plain] INFO scoped plain span=work"),
+        "output:\n{output}"
+    );
+    assert!(!output.contains("after guard"), "output:\n{output}");
+    assert!(!traxer::is_initialized());
+}
+
+#[test]
+fn set_default_applies_json_config_to_current_thread() {
+    let path = temp_log("json");
+    let cfg = traxer::Config::new("traxer-scoped-json")
+        .json()
+        .with_filter_directives("info")
+        .stream(traxer::Stream::File(traxer::FileSink::new(&path)));
+
+    {
+        let _guard = traxer::set_default(cfg).expect("set_default should succeed");
+        traxer::info!("scoped json");
+    }
+
+    let output = fs::read_to_string(&path).expect("log file should exist");
+    let line: serde_json::Value = serde_json::from_str(output.trim()).expect("invalid json line");
+    assert_eq!(line["name"], "traxer-scoped-json");
+    assert_eq!(line["message"], "scoped json");
+    assert!(line.get("span").is_none());
+}
@@ -0,0 +1,69 @@
+#![cfg(all(feature = "signal", unix))]
+
+use signal_hook::{
+    consts::{SIGUSR1, SIGUSR2},
+    low_level::raise,
+};
+use std::{
+    fs,
+    time::{Duration, Instant},
+};
+
+fn wait_for(mut condition: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        if condition() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    false
+}
+
+#[test]
+fn signals_step_verbosity_up_and_down() {
+    // SAFETY: this is the only test in this binary, so no other thread reads
+    // the environment concurrently.
+    unsafe { std::env::remove_var("RUST_LOG") };
+    let dir = std::env::temp_dir().join(format!("traxer-signal-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("app.log");
+
+    let cfg = traxer::Config::new("traxer-signal")
+        .error_report(false)
+        .signal_verbosity(true)
+        .stream(traxer::Stream::File(traxer::FileSink::new(&path)));
+    let handle = traxer::try_init(cfg).expect("try_init should succeed");
+
+    raise(SIGUSR1).expect("failed to raise SIGUSR1");
+    assert!(wait_for(|| handle.verbose() == 1));
+    raise(SIGUSR1).expect("failed to raise SIGUSR1");
+    assert!(wait_for(|| handle.verbose() == 2));
+    raise(SIGUSR2).expect("failed to raise SIGUSR2");
+    assert!(wait_for(|| handle.verbose() == 1));
+    raise(SIGUSR2).expect("failed to raise SIGUSR2");
+    assert!(wait_for(|| handle.verbose() == 0));
+    raise(SIGUSR2).expect("failed to raise SIGUSR2");
+    assert!(wait_for(|| {
+        fs::read_to_string(&path)
+            .unwrap_or_default()
+            .matches("signal=\"SIGUSR2\" level=\"info\"")
+            .count()
+            == 2
+    }));
+    raise(SIGUSR1).expect("failed to raise SIGUSR1");
+    assert!(wait_for(|| handle.verbose() == 1));
+    assert_eq!(handle.quiet(), 0);
+
+    let output = fs::read_to_string(&path).expect("log file should exist");
+    assert!(
+        output.contains("log verbosity changed signal=\"SIGUSR1\" level=\"debug\""),
+        "output:\n{output}"
+    );
+    assert!(
+        output.contains("INFO log verbosity changed signal=\"SIGUSR2\" level=\"info\""),
+        "output:\n{output}"
+    );
+
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,56 @@
+#![cfg(all(feature = "signal", unix))]
+
+use signal_hook::{
+    consts::{SIGUSR1, SIGUSR2},
+    low_level::raise,
+};
+use std::{
+    fs,
+    time::{Duration, Instant},
+};
+
+fn wait_for(mut condition: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        if condition() {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    false
+}
+
+#[test]
+fn signals_restore_rust_log_directives() {
+    let dir = std::env::temp_dir().join(format!("traxer-signal-env-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("app.log");
+
+    // SAFETY: this is the only test in this binary, so no other thread reads
+    // the environment concurrently.
+    unsafe { std::env::set_var("RUST_LOG", "signal_env=trace") };
+    let cfg = traxer::Config::new("traxer-signal-env")
+        .error_report(false)
+        .signal_verbosity(true)
+        .stream(traxer::Stream::File(traxer::FileSink::new(&path)));
+    let handle = traxer::try_init(cfg).expect("try_init should succeed");
+
+    raise(SIGUSR1).expect("failed to raise SIGUSR1");
+    assert!(wait_for(|| handle.verbose() == 1));
+    assert_eq!(
+        handle.filter_directives().as_deref(),
+        Some("debug,signal_env=trace")
+    );
+    raise(SIGUSR2).expect("failed to raise SIGUSR2");
+    assert!(wait_for(|| handle.verbose() == 0));
+    assert_eq!(
+        handle.filter_directives().as_deref(),
+        Some("signal_env=trace")
+    );
+
+    traxer::trace!("trace after signals");
+    let output = fs::read_to_string(&path).expect("log file should exist");
+    assert!(output.contains("trace after signals"), "output:\n{output}");
+
+    let _ = fs::remove_dir_all(&dir);
+}
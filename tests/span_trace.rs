@@ -0,0 +1,31 @@
+use tracing_error::{SpanTrace, SpanTraceStatus};
+
+#[test]
+fn error_report_captures_active_span_trace() {
+    let cfg = traxer::Config::new("traxer-spantrace")
+        .with_filter_directives("info")
+        .stream(traxer::Stream::custom(std::io::sink));
+    let _guard = traxer::set_default(cfg).expect("set_default should succeed");
+
+    let span = tracing::info_span!("deploy", env = "prod");
+    let trace = span.in_scope(SpanTrace::capture);
+
+    assert_eq!(trace.status(), SpanTraceStatus::CAPTURED);
+    let rendered = trace.to_string();
+    assert!(rendered.contains("deploy"), "trace:\n{rendered}");
+    assert!(rendered.contains("env"), "trace:\n{rendered}");
+}
+
+#[test]
+fn disabling_error_report_skips_span_trace_capture() {
+    let cfg = traxer::Config::new("traxer-spantrace")
+        .with_filter_directives("info")
+        .error_report(false)
+        .stream(traxer::Stream::custom(std::io::sink));
+    let _guard = traxer::set_default(cfg).expect("set_default should succeed");
+
+    let span = tracing::info_span!("deploy");
+    let trace = span.in_scope(SpanTrace::capture);
+
+    assert_eq!(trace.status(), SpanTraceStatus::UNSUPPORTED);
+}
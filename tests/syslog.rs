@@ -0,0 +1,87 @@
+#![cfg(unix)]
+
+use std::{os::unix::net::UnixDatagram, time::Duration};
+
+fn socket(name: &str) -> (UnixDatagram, std::path::PathBuf) {
+    let path = std::env::temp_dir().join(format!("traxer-{name}-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let socket = UnixDatagram::bind(&path).expect("bind syslog stand-in");
+    socket
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .expect("set timeout");
+    (socket, path)
+}
+
+fn recv(socket: &UnixDatagram) -> String {
+    let mut buf = [0u8; 4096];
+    let len = socket.recv(&mut buf).expect("datagram");
+    String::from_utf8(buf[..len].to_vec()).expect("utf-8 datagram")
+}
+
+#[test]
+fn syslog_stream_sends_rfc5424_datagrams() {
+    let (server, path) = socket("syslog-5424");
+    let cfg = traxer::Config::new("traxer-cron")
+        .with_filter_directives("info")
+        .policy(traxer::Policy::default_auto())
+        .stream(traxer::Stream::Syslog(
+            traxer::SyslogSink::new()
+                .path(&path)
+                .facility(traxer::Facility::Cron),
+        ));
+
+    {
+        let _guard = traxer::set_default(cfg).expect("set_default should succeed");
+        traxer::info!(job = "backup", "job started");
+        traxer::error!("job failed");
+    }
+
+    let pid = std::process::id();
+    let info = recv(&server);
+    assert!(info.starts_with("<78>1 "), "{info}");
+    assert!(info.contains(&format!(" traxer-cron {pid} - - ")), "{info}");
+    let body = info.split(" - - ").nth(1).expect("message body");
+    let line: serde_json::Value = serde_json::from_str(body).expect("invalid json body");
+    assert_eq!(line["job"], "backup");
+    assert_eq!(line["message"], "job started");
+
+    let error = recv(&server);
+    assert!(error.starts_with("<75>1 "), "{error}");
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn syslog_stream_supports_rfc3164() {
+    let (server, path) = socket("syslog-3164");
+    let cfg = traxer::Config::new("traxer-cron")
+        .with_filter_directives("warn")
+        .stream(traxer::Stream::Syslog(
+            traxer::SyslogSink::new().path(&path).rfc3164(),
+        ));
+
+    {
+        let _guard = traxer::set_default(cfg).expect("set_default should succeed");
+        traxer::warn!("disk almost full");
+    }
+
+    let datagram = recv(&server);
+    assert!(datagram.starts_with("<12>"), "{datagram}");
+    assert!(
+        datagram.contains(&format!(" traxer-cron[{}]: ", std::process::id())),
+        "{datagram}"
+    );
+    assert!(datagram.contains("disk almost full"), "{datagram}");
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn syslog_stream_reports_missing_socket() {
+    let cfg = traxer::Config::new("traxer-cron").stream(traxer::Stream::Syslog(
+        traxer::SyslogSink::new().path("/nonexistent/traxer.sock"),
+    ));
+
+    let err = traxer::set_default(cfg).expect_err("missing socket should fail");
+    assert!(matches!(err, traxer::InitError::ConnectSyslog(_)), "{err}");
+}